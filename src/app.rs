@@ -1,11 +1,43 @@
 use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ffi::OsString,
     fs,
-    process::{Child, Command},
-    path::PathBuf,
+    io,
+    process::{Child, Command, Stdio},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    time::{Duration, Instant},
 };
 use dirs;
 use serde::{Deserialize, Serialize};
-use crossterm::event::{KeyEvent, KeyCode};
+use serde_json::json;
+use crossterm::event::{KeyEvent, KeyCode, KeyModifiers};
+
+use crate::history::History;
+use crate::logging::LogView;
+use crate::toast::{Level, Toasts};
+use crate::storage::{self, InstanceLock};
+use crate::schema::{self, PlaylistLoad};
+use crate::cli;
+use crate::tools::album_art::AlbumArt;
+use crate::tools::audio_device::{self, AudioDevice};
+use crate::tools::cache::{AudioCache, CacheEvent};
+use crate::tools::control::{ControlCommand, VolumeChange};
+use crate::tools::downloader::{Downloader, JobState, Notice, Preset};
+use crate::tools::flat_playlist::{self, FlatPlaylist};
+use crate::tools::metadata::Lookup;
+use crate::tools::dedupe;
+use crate::tools::file_watch::FileWatcher;
+use crate::config::Config;
+use crate::tools::scrobble::{Listen, ListenTracker, ScrobbleEvent, Scrobbler};
+use crate::tools::notify::{NotifyAction, Notifier};
+use crate::tools::mpris::{MprisCommand, MprisServer, MprisState, PlaybackStatus};
+use crate::tools::mpv_ipc;
+use crate::tools::mpv_output::{self, OutputLine, TrackLog};
+use crate::tools::podcast::{self, PodcastEvent, PodcastStore};
+use crate::tools::radio::{IcyWatcher, RadioEvent, RadioHistory};
+use crate::tools::source::{SongSource, SourceError};
+use crate::widgets::text_input::TextInput;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppState {
@@ -14,6 +46,10 @@ pub enum AppState {
     VideoPlayer,
     FileTools,
     ComingSoon,
+    Messages,
+    Logs,
+    Settings,
+    Downloads,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -21,10 +57,21 @@ pub enum MenuItem {
     MusicPlayer,
     VideoPlayer,
     FileTools,
+    Downloads,
     ComingSoon,
+    Settings,
+    Logs,
     Quit,
 }
 
+/// Input yang sedang terbuka di layar Downloads
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DownloadPrompt {
+    Url,
+    /// Nama playlist tujuan; kosong berarti playlist utama
+    Playlist,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MusicPlayerMode {
     Browse,
@@ -32,14 +79,85 @@ pub enum MusicPlayerMode {
     AddSong,
     EditSong,
     DeleteConfirm,
+    Prompt,
+    Duplicates,
+    Podcasts,
+    /// Konfirmasi berhenti berlangganan podcast terpilih
+    Unsubscribe,
+    Episodes,
+    ReloadConflict,
+    Diagnostics,
+    ImportPreview,
+}
+
+/// Aksi yang menunggu input satu baris dari user
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    MoveToPlaylist,
+    ExportM3u,
+    SetArtist,
+    SetTitle,
+    SubscribeFeed,
+    ImportYtdlp,
+}
+
+/// Isi dump yt-dlp yang sedang ditinjau sebelum dimasukkan ke playlist
+pub struct ImportPreview {
+    pub title: Option<String>,
+    pub entries: Vec<flat_playlist::Entry>,
+    pub selected: Vec<bool>,
+    /// Entri yang sudah ada di playlist tujuan; dilewati saat import
+    pub existing: Vec<bool>,
+    pub cursor: usize,
+    /// Nama playlist tujuan; kosong berarti playlist utama
+    pub target: TextInput,
+    pub editing_target: bool,
+}
+
+impl ImportPreview {
+    pub fn selected_count(&self) -> usize {
+        self.selected.iter().filter(|selected| **selected).count()
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Song {
     pub title:  String,
     pub artist: String,
     pub url:    String,
     pub path:   Option<String>,
+    /// Stasiun radio internet: metadata ICY dibaca dan stream di-reconnect otomatis
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub radio:  bool,
+    /// Kecepatan putar terakhir untuk lagu ini; `None` berarti 1.0x
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed:  Option<f64>,
+    /// Durasi dalam detik, dari metadata yt-dlp/tag file atau `#EXTINF`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+impl Song {
+    /// Buat lagu dari input form/CLI. File lokal disimpan di `path`,
+    /// sisanya di `url`; stream otomatis ditandai sebagai radio.
+    pub fn from_input(title: &str, artist: &str, input: &str) -> Result<Self, SourceError> {
+        let input = input.trim();
+        let source = SongSource::parse(input)?;
+        let (url, path) = if source.is_local() {
+            (String::new(), Some(input.to_string()))
+        } else {
+            (input.to_string(), None)
+        };
+        Ok(Song {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            url,
+            path,
+            radio: matches!(source, SongSource::Stream(_)),
+            speed: None,
+            duration: None,
+        })
+    }
 }
 
 pub struct App {
@@ -54,47 +172,238 @@ pub struct App {
     pub selected_song_index: usize,
     pub volume: u8,
     pub player_process: Option<std::process::Child>,
-    pub form_title: String,
-    pub form_artist: String,
-    pub form_url: String,
+    pub form_title: TextInput,
+    pub form_artist: TextInput,
+    pub form_url: TextInput,
     pub form_field_index: usize,
+    /// Title/Artist yang sudah diisi user; metadata otomatis tidak menimpanya
+    form_touched: [bool; 2],
+    /// Durasi dari metadata sumber, ikut disimpan saat form di-submit
+    pub form_duration: Option<u64>,
+    /// Pencarian metadata untuk isi field URL yang sedang berjalan
+    pub metadata_lookup: Option<Lookup>,
+    /// Lookup ditunda sampai user berhenti mengetik
+    metadata_due: Option<Instant>,
     pub video_playing: bool,
     pub current_video: Option<String>,
     pub current_directory: String,
+    pub history: History<PlaylistSnapshot>,
+    pub toasts: Toasts,
+    /// Layar yang dibuka lagi saat keluar dari Messages
+    pub messages_return: AppState,
+    pub selected_message: usize,
+    pub log_view: LogView,
+    /// Output audio yang ditemukan mpv, untuk layar Settings
+    pub audio_devices: Vec<AudioDevice>,
+    pub selected_device: usize,
+    /// Pencarian device yang sedang berjalan
+    device_scan: Option<Receiver<Result<Vec<AudioDevice>, String>>>,
+    pub device_error: Option<String>,
+    settings_return: AppState,
+    /// Tool Downloads: antrean yt-dlp
+    pub downloader: Downloader,
+    pub selected_job: usize,
+    pub download_preset: Preset,
+    pub download_prompt: Option<DownloadPrompt>,
+    pub download_input: TextInput,
+    pub import: Option<ImportPreview>,
+    pub marked: HashSet<usize>,
+    pub mark_anchor: Option<usize>,
+    pub queue: VecDeque<Song>,
+    pub playing_song: Option<Song>,
+    /// Jenis sumber lagu yang diputar, dihitung sekali saat mulai putar
+    /// (`SongSource::of` memeriksa filesystem untuk path lokal)
+    pub playing_source_kind: Option<&'static str>,
+    /// Kecepatan putar lagu yang sedang diputar
+    pub speed: f64,
+    /// Titik A-B loop (detik); loop aktif kalau keduanya terisi
+    pub loop_a: Option<f64>,
+    pub loop_b: Option<f64>,
+    /// Kecepatan yang diubah belum disimpan; ditunda supaya menekan `]`
+    /// berkali-kali tidak menggeser semua backup playlist
+    speed_save_due: Option<Instant>,
+    pub prompt_kind: PromptKind,
+    pub prompt_input: TextInput,
+    pub form_duplicate_ack: Option<String>,
+    pub duplicate_groups: Vec<Vec<usize>>,
+    pub selected_duplicate: usize,
+    pub source_errors: HashMap<String, String>,
+    /// Output mpv per sumber lagu (kunci sama dengan `source_errors`)
+    pub diagnostics: HashMap<String, TrackLog>,
+    /// Pembaca output mpv yang sedang jalan, beserta kunci lagunya
+    mpv_output: Option<(String, Receiver<OutputLine>)>,
+    /// Lagu yang ditampilkan di panel diagnostik
+    pub diagnostics_song: Option<Song>,
+    /// Jarak scroll dari baris terbawah; 0 berarti ikut output terbaru
+    pub diagnostics_scroll: usize,
+    diagnostics_return: MusicPlayerMode,
+    pub radio_watcher: Option<IcyWatcher>,
+    pub station_name: Option<String>,
+    pub stream_title: Option<String>,
+    pub radio_history: RadioHistory,
+    pub radio_reconnect_at: Option<Instant>,
+    pub radio_attempts: u32,
+    pub podcasts: PodcastStore,
+    /// Salinan offline lagu URL
+    pub cache: AudioCache,
+    /// Cover lagu lokal di NowPlaying
+    pub album_art: AlbumArt,
+    pub selected_podcast: usize,
+    pub selected_episode: usize,
+    /// Download yang sedang berjalan: guid -> (byte terunduh, total)
+    pub downloads: HashMap<String, (u64, Option<u64>)>,
+    pub refreshing_feeds: usize,
+    /// Episode yang sedang diputar: (URL feed, guid)
+    pub playing_episode: Option<(String, String)>,
+    pub last_position_poll: Instant,
+    podcast_tx: Sender<PodcastEvent>,
+    podcast_rx: Receiver<PodcastEvent>,
+    /// Service MPRIS2; `None` kalau tidak ada session bus
+    pub mpris: Option<MprisServer>,
+    /// Naik setiap kali lagu baru diputar
+    pub track_serial: u64,
+    pub config: Config,
+    /// Desktop notification; `None` kalau dimatikan di config atau tidak ada session bus
+    pub notifier: Option<Notifier>,
+    pub scrobbler: Option<Scrobbler>,
+    /// Waktu dengar lagu yang sedang diputar, untuk aturan scrobble 50%/4 menit
+    pub listen_tracker: Option<ListenTracker>,
+    pub last_tick: Instant,
+    /// Lock `playlist.json.lock`, dipegang sampai aplikasi keluar
+    _playlist_lock: Option<InstanceLock>,
+    /// Instance lain sudah berjalan; playlist tidak disimpan supaya tidak saling menimpa
+    pub read_only: bool,
+    /// Kegagalan simpan terakhir, ditampilkan sampai simpan berikutnya berhasil
+    pub save_error: Option<String>,
+    /// `playlist.json.1..N` sudah digeser di sesi ini
+    backups_rotated: bool,
+    /// Inotify untuk `playlist.json`, supaya edit dari luar langsung terbaca
+    playlist_watcher: Option<FileWatcher>,
+    /// Isi `playlist.json` terakhir yang diketahui (dibaca atau ditulis sendiri)
+    pub disk_playlist: Vec<Song>,
+    /// Versi dari disk yang belum diterapkan: menunggu mode aman atau jawaban user
+    pub pending_reload: Option<Vec<Song>>,
+    reload_due: Option<Instant>,
+}
+
+/// Interval menyimpan posisi resume episode podcast
+const POSITION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tunggu sebentar setelah event inotify terakhir; editor sering menulis
+/// file dalam beberapa langkah
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Batas dan langkah kecepatan putar (pitch tetap dikoreksi mpv)
+pub const MIN_SPEED: f64 = 0.5;
+pub const MAX_SPEED: f64 = 2.0;
+const SPEED_STEP: f64 = 0.1;
+const SPEED_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// Jeda setelah field URL berubah sebelum metadata dicari
+const METADATA_DELAY: Duration = Duration::from_millis(600);
+
+/// State yang disimpan history: playlist aktif plus playlist lain
+/// (berdasarkan nama) yang ikut berubah, misalnya saat memindahkan lagu.
+#[derive(Clone)]
+pub struct PlaylistSnapshot {
+    pub songs: Vec<Song>,
+    pub others: Vec<(String, Vec<Song>)>,
+}
+
+impl PlaylistSnapshot {
+    fn other_names(&self) -> Vec<String> {
+        self.others.iter().map(|(name, _)| name.clone()).collect()
+    }
 }
 
+/// Jumlah pesan yang disimpan untuk layar Messages
+const TOAST_HISTORY: usize = 200;
+
 impl App {
     pub fn new() -> Self {
         let menu_items = vec![
             MenuItem::MusicPlayer,
             MenuItem::VideoPlayer,
             MenuItem::FileTools,
+            MenuItem::Downloads,
             MenuItem::ComingSoon,
+            MenuItem::Settings,
+            MenuItem::Logs,
             MenuItem::Quit,
         ];
 
-        let playlist = Self::load_playlist().unwrap_or_else(|| {
-            vec![
-                Song {
-                    title: "Indonesian Folk Music Medley 2024 Ver. - hololive ID [Cover]".into(),
-                    artist: "hololive ID".into(),
-                    url: "https://youtu.be/rjhIMMSolmc?feature=shared".into(),
-                    path: None,
-                },
-                Song {
-                    title: "Terhebat - hololive ID [Cover]".into(),
-                    artist: "hololive ID".into(),
-                    url: "https://youtu.be/PaOMF-g1ZWU?feature=shared".into(),
-                    path: None,
-                },
-                Song {
-                    title: "Bebas - hololive ID [Cover]".into(),
-                    artist: "hololive ID".into(),
-                    url: "https://youtu.be/wlyRGXUwjVA?feature=shared".into(),
-                    path: None,
-                },
-            ]
-        });
+        let (playlist_lock, locked) = match Self::playlist_lock_path().map(|path| InstanceLock::acquire(&path)) {
+            Some(Ok(Some(lock))) => (Some(lock), false),
+            Some(Ok(None)) => (None, true),
+            // Lock tidak bisa dibuat (mis. config dir read-only); tetap coba simpan
+            _ => (None, false),
+        };
+
+        // Playlist yang gagal dibaca tidak pernah diganti dengan playlist contoh
+        let (playlist, load_error, unreadable) = match Self::load_playlist() {
+            PlaylistLoad::Missing => (Self::default_playlist(), None, false),
+            PlaylistLoad::Loaded(songs) => (songs, None, false),
+            PlaylistLoad::Recovered { songs, quarantined, backup, error } => {
+                let message = format!(
+                    "playlist.json was corrupt ({}); moved to {} and restored from {}",
+                    error,
+                    quarantined.display(),
+                    backup.display()
+                );
+                (songs, Some(message), false)
+            }
+            PlaylistLoad::Quarantined { quarantined, error } => {
+                let message = format!(
+                    "playlist.json was corrupt ({}); moved to {}, starting with an empty playlist",
+                    error,
+                    quarantined.display()
+                );
+                (Vec::new(), Some(message), false)
+            }
+            PlaylistLoad::Unreadable(message) => {
+                (Vec::new(), Some(format!("{}; playlist changes will not be saved", message)), true)
+            }
+        };
+        let read_only = locked || unreadable;
+        // Masalah saat startup masuk ke scrollback Messages
+        let mut toasts = Toasts::new(TOAST_HISTORY);
+        if let Some(error) = &load_error {
+            toasts.push(Level::Error, error.clone());
+        }
+        let save_error = if locked {
+            Some(String::from("Another terminus instance is running; playlist changes will not be saved"))
+        } else {
+            load_error
+        };
+
+        let playlist_watcher = match Self::playlist_path().map(|path| FileWatcher::start(&path)) {
+            Some(Ok(watcher)) => Some(watcher),
+            Some(Err(e)) => {
+                toasts.push(Level::Warn, format!("Cannot watch playlist.json for changes: {}", e));
+                None
+            }
+            None => None,
+        };
+
+        let (podcast_tx, podcast_rx) = mpsc::channel();
+        let (config, config_error) = Config::load();
+        if let Some(error) = config_error {
+            toasts.push(Level::Error, error);
+        }
+        let notifier = if config.notifications {
+            Notifier::start()
+                .map_err(|e| toasts.push(Level::Warn, format!("Desktop notifications unavailable: {}", e)))
+                .ok()
+        } else {
+            None
+        };
+        // Tanpa session bus MPRIS memang tidak tersedia; cukup info
+        let mpris = MprisServer::start()
+            .map_err(|e| toasts.push(Level::Info, format!("MPRIS unavailable: {}", e)))
+            .ok();
+        let scrobbler = config.scrobble.enabled.then(|| Scrobbler::spawn(config.scrobble.clone()));
+        // Lagu yang di-pin tapi file-nya hilang langsung diunduh lagi
+        let mut cache = AudioCache::open(AudioCache::default_dir());
+        cache.resume_pinned();
 
         Self {
             state: AppState::MainMenu,
@@ -104,47 +413,223 @@ impl App {
             music_playing: false,
             current_song: None,
             mp_mode: MusicPlayerMode::Browse,
-            playlist,
+            playlist: playlist.clone(),
             selected_song_index: 0,
             volume: 80,
             player_process: None,
-            form_title: String::new(),
-            form_artist: String::new(),
-            form_url: String::new(),
+            form_title: TextInput::new(),
+            form_artist: TextInput::new(),
+            form_url: TextInput::new(),
             form_field_index: 0,
+            form_touched: [false; 2],
+            form_duration: None,
+            metadata_lookup: None,
+            metadata_due: None,
             video_playing: false,
             current_video: None,
             current_directory: "~/".into(),
+            history: History::new(100),
+            toasts,
+            messages_return: AppState::MainMenu,
+            selected_message: 0,
+            audio_devices: Vec::new(),
+            selected_device: 0,
+            device_scan: None,
+            device_error: None,
+            settings_return: AppState::MainMenu,
+            downloader: Downloader::new(),
+            selected_job: 0,
+            download_preset: config.downloads.preset,
+            download_prompt: None,
+            download_input: TextInput::new(),
+            import: None,
+            log_view: LogView::new(),
+            marked: HashSet::new(),
+            mark_anchor: None,
+            queue: VecDeque::new(),
+            playing_song: None,
+            playing_source_kind: None,
+            speed: 1.0,
+            loop_a: None,
+            loop_b: None,
+            speed_save_due: None,
+            prompt_kind: PromptKind::MoveToPlaylist,
+            prompt_input: TextInput::new(),
+            form_duplicate_ack: None,
+            duplicate_groups: Vec::new(),
+            selected_duplicate: 0,
+            source_errors: HashMap::new(),
+            diagnostics: HashMap::new(),
+            mpv_output: None,
+            diagnostics_song: None,
+            diagnostics_scroll: 0,
+            diagnostics_return: MusicPlayerMode::Browse,
+            radio_watcher: None,
+            station_name: None,
+            stream_title: None,
+            radio_history: RadioHistory::load(),
+            radio_reconnect_at: None,
+            radio_attempts: 0,
+            podcasts: PodcastStore::load(),
+            cache,
+            album_art: AlbumArt::new(config.album_art),
+            selected_podcast: 0,
+            selected_episode: 0,
+            downloads: HashMap::new(),
+            refreshing_feeds: 0,
+            playing_episode: None,
+            last_position_poll: Instant::now(),
+            podcast_tx,
+            podcast_rx,
+            mpris,
+            track_serial: 0,
+            config,
+            notifier,
+            scrobbler,
+            listen_tracker: None,
+            last_tick: Instant::now(),
+            _playlist_lock: playlist_lock,
+            read_only,
+            save_error,
+            backups_rotated: false,
+            playlist_watcher,
+            disk_playlist: playlist,
+            pending_reload: None,
+            reload_due: None,
         }
     }
 
-    pub fn load_playlist() -> Option<Vec<Song>> {
-        let config_dir = dirs::config_dir()?;
-        let playlist_path = config_dir.join("terminus").join("playlist.json");
-        
-        if playlist_path.exists() {
-            let content = fs::read_to_string(playlist_path).ok()?;
-            serde_json::from_str(&content).ok()
-        } else {
-            None
+    /// Playlist contoh kalau belum ada `playlist.json`
+    pub fn default_playlist() -> Vec<Song> {
+        vec![
+            Song {
+                title: "Indonesian Folk Music Medley 2024 Ver. - hololive ID [Cover]".into(),
+                artist: "hololive ID".into(),
+                url: "https://youtu.be/rjhIMMSolmc?feature=shared".into(),
+                path: None,
+                radio: false,
+                speed: None,
+                duration: None,
+            },
+            Song {
+                title: "Terhebat - hololive ID [Cover]".into(),
+                artist: "hololive ID".into(),
+                url: "https://youtu.be/PaOMF-g1ZWU?feature=shared".into(),
+                path: None,
+                radio: false,
+                speed: None,
+                duration: None,
+            },
+            Song {
+                title: "Bebas - hololive ID [Cover]".into(),
+                artist: "hololive ID".into(),
+                url: "https://youtu.be/wlyRGXUwjVA?feature=shared".into(),
+                path: None,
+                radio: false,
+                speed: None,
+                duration: None,
+            },
+        ]
+    }
+
+    /// Folder untuk playlist bernama selain playlist utama
+    pub fn playlists_dir() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("terminus").join("playlists"))
+    }
+
+    /// Nama semua playlist di `playlists_dir`, urut alfabet
+    pub fn list_named_playlists() -> Vec<String> {
+        let mut names: Vec<String> = Self::playlists_dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Playlist bernama; kosong kalau belum ada, error kalau file-nya rusak
+    pub fn load_named_playlist(name: &str) -> Result<Vec<Song>, Box<dyn std::error::Error>> {
+        let dir = Self::playlists_dir().ok_or("Config directory not found")?;
+        Ok(schema::read_songs(&dir.join(format!("{}.json", name)))?.unwrap_or_default())
+    }
+
+    pub fn save_named_playlist(name: &str, songs: &[Song]) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = Self::playlists_dir().ok_or("Config directory not found")?;
+        let playlist_json = schema::encode_songs(songs)?;
+        storage::atomic_write(&dir.join(format!("{}.json", name)), playlist_json.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn playlist_path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("terminus").join("playlist.json"))
+    }
+
+    /// Lock yang dipegang instance TUI selama berjalan
+    pub fn playlist_lock_path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("terminus").join("playlist.json.lock"))
+    }
+
+    /// Baca playlist utama. Format lama dimigrasikan di memori dan ditulis
+    /// ulang dalam format baru saat penyimpanan berikutnya.
+    pub fn load_playlist() -> PlaylistLoad {
+        match Self::playlist_path() {
+            Some(path) => schema::load_playlist_file(&path),
+            None => PlaylistLoad::Missing,
         }
     }
     
-    pub fn save_playlist(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_dir = dirs::config_dir().ok_or("Config directory not found")?;
-        let terminus_dir = config_dir.join("terminus");
-        
-        if !terminus_dir.exists() {
-            fs::create_dir_all(&terminus_dir)?;
-        }
-        
-        let playlist_path = terminus_dir.join("playlist.json");
-        let playlist_json = serde_json::to_string_pretty(&self.playlist)?;
-        
-        fs::write(playlist_path, playlist_json)?;
+    /// Backup hanya dirotasi di penyimpanan pertama sesi ini, jadi J/K atau
+    /// retag berturut-turut tidak mendorong keluar backup dari sesi sebelumnya
+    pub fn save_playlist(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.read_only {
+            return Err("playlist is open in another terminus instance".into());
+        }
+        Self::write_playlist(&self.playlist, !self.backups_rotated)?;
+        self.backups_rotated = true;
+        Ok(())
+    }
+
+    /// Tulis playlist utama; dipakai juga oleh subcommand CLI. Dengan `rotate`,
+    /// isi lama digeser ke `playlist.json.1..N` sebelum file baru di-rename ke
+    /// tempatnya.
+    pub fn write_playlist(songs: &[Song], rotate: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let playlist_path = Self::playlist_path().ok_or("Config directory not found")?;
+        let playlist_json = schema::encode_songs(songs)?;
+
+        if rotate {
+            storage::rotate_backups(&playlist_path, storage::BACKUP_COUNT)?;
+        }
+        storage::atomic_write(&playlist_path, playlist_json.as_bytes())?;
         Ok(())
     }
 
+    /// Simpan playlist utama; kalau gagal, pesannya tetap tampil di UI
+    /// sampai penyimpanan berikutnya berhasil
+    /// Simpan playlist utama; `false` kalau gagal (error sudah ditampilkan)
+    pub fn persist_playlist(&mut self) -> bool {
+        match self.save_playlist() {
+            Ok(()) => {
+                self.save_error = None;
+                self.disk_playlist = self.playlist.clone();
+                true
+            }
+            Err(e) => {
+                let message = format!("Playlist not saved: {}", e);
+                self.set_error(message.clone());
+                self.save_error = Some(message);
+                false
+            }
+        }
+    }
+
     pub fn next(&mut self) {
         self.index = (self.index + 1) % self.menu_items.len();
         self.selected_item = self.menu_items[self.index];
@@ -164,14 +649,23 @@ impl App {
         match self.mp_mode {
             MusicPlayerMode::Browse => self.handle_browse_mode(key),
             MusicPlayerMode::NowPlaying => self.handle_now_playing_mode(key),
-            MusicPlayerMode::AddSong => self.handle_add_song_mode(key),
-            MusicPlayerMode::EditSong => self.handle_edit_song_mode(key),
+            MusicPlayerMode::AddSong | MusicPlayerMode::EditSong => self.handle_song_form_mode(key),
             MusicPlayerMode::DeleteConfirm => self.handle_delete_confirm_mode(key),
+            MusicPlayerMode::Prompt => self.handle_prompt_mode(key),
+            MusicPlayerMode::Duplicates => self.handle_duplicates_mode(key),
+            MusicPlayerMode::Podcasts => self.handle_podcasts_mode(key),
+            MusicPlayerMode::Unsubscribe => self.handle_unsubscribe_mode(key),
+            MusicPlayerMode::Episodes => self.handle_episodes_mode(key),
+            MusicPlayerMode::ReloadConflict => self.handle_reload_conflict_mode(key),
+            MusicPlayerMode::Diagnostics => self.handle_diagnostics_mode(key),
+            MusicPlayerMode::ImportPreview => self.handle_import_preview_mode(key),
         }
     }
     
     fn handle_browse_mode(&mut self, key: KeyEvent) {
         match key.code {
+            KeyCode::Char('!') => self.open_messages(),
+            KeyCode::Char('i') => self.open_diagnostics(),
             KeyCode::Down => {
                 if !self.playlist.is_empty() {
                     self.selected_song_index = (self.selected_song_index + 1) % self.playlist.len();
@@ -194,20 +688,27 @@ impl App {
             },
             KeyCode::Char('a') => {
                 // Reset form sebelum menambah lagu baru
-                self.form_title = String::new();
-                self.form_artist = String::new();
-                self.form_url = String::new();
+                self.form_title.clear();
+                self.form_artist.clear();
+                self.form_url.clear();
                 self.form_field_index = 0;
+                self.form_duplicate_ack = None;
+                self.reset_form_metadata([false; 2], None);
                 self.mp_mode = MusicPlayerMode::AddSong;
             },
             KeyCode::Char('e') => {
                 if !self.playlist.is_empty() {
                     if let Some(song) = self.playlist.get(self.selected_song_index) {
                         // Pra-isi form dengan data lagu yang akan diedit
-                        self.form_title = song.title.clone();
-                        self.form_artist = song.artist.clone();
-                        self.form_url = song.url.clone();
+                        self.form_title = TextInput::with_value(song.title.clone());
+                        self.form_artist = TextInput::with_value(song.artist.clone());
+                        let source = song.path.clone().unwrap_or_else(|| song.url.clone());
+                        self.form_url = TextInput::with_value(source);
                         self.form_field_index = 0;
+                        self.form_duplicate_ack = None;
+                        let touched = [!song.title.is_empty(), !song.artist.is_empty()];
+                        let duration = song.duration;
+                        self.reset_form_metadata(touched, duration);
                         self.mp_mode = MusicPlayerMode::EditSong;
                     }
                 }
@@ -217,272 +718,2244 @@ impl App {
                     self.mp_mode = MusicPlayerMode::DeleteConfirm;
                 }
             },
+            KeyCode::Char('K') => self.move_selected_song(-1),
+            KeyCode::Char('J') => self.move_selected_song(1),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => self.redo(),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('V') => self.mark_range(),
+            KeyCode::Char('*') => self.invert_marks(),
+            KeyCode::Char('Q') => self.enqueue_targets(),
+            KeyCode::Char('m') => self.open_prompt(PromptKind::MoveToPlaylist),
+            KeyCode::Char('x') => self.open_prompt(PromptKind::ExportM3u),
+            KeyCode::Char('I') => self.open_prompt(PromptKind::ImportYtdlp),
+            KeyCode::Char('t') => self.open_prompt(PromptKind::SetArtist),
+            KeyCode::Char('T') => self.open_prompt(PromptKind::SetTitle),
+            KeyCode::Char('D') => self.open_duplicates(),
+            KeyCode::Char('R') => self.toggle_radio_targets(),
+            KeyCode::Char('O') => self.toggle_offline_targets(),
+            KeyCode::Char('P') => {
+                self.mp_mode = MusicPlayerMode::Podcasts;
+            },
             _ => {}
         }
     }
-    
-    fn handle_now_playing_mode(&mut self, key: KeyEvent) {
+
+    /// Tandai/hapus tanda stasiun radio pada lagu target
+    fn toggle_radio_targets(&mut self) {
+        let indices = self.target_indices();
+        if indices.is_empty() {
+            return;
+        }
+        let radio = !indices.iter().all(|&i| self.playlist[i].radio);
+        let description = format!("radio toggle of {}", self.describe_targets());
+        let before = self.snapshot(&[]);
+        for i in indices {
+            self.playlist[i].radio = radio;
+        }
+        self.clear_marks();
+        self.commit_edit(description, before);
+    }
+
+    /// Pin lagu yang ditandai (atau terpilih) untuk didengar offline; kalau
+    /// semuanya sudah di-pin, lepas pin-nya
+    fn toggle_offline_targets(&mut self) {
+        let keys: Vec<String> = self
+            .target_indices()
+            .iter()
+            .filter_map(|&i| AudioCache::key(&self.playlist[i]))
+            .collect();
+        if keys.is_empty() {
+            self.set_warning("Only URL songs can be kept offline (not local files or radio)");
+            return;
+        }
+        let pin = !keys.iter().all(|key| self.cache.is_pinned(key));
+        let mut started = 0;
+        for key in &keys {
+            self.cache.set_pinned(key, pin);
+            if pin && self.cache.request(key) {
+                started += 1;
+            }
+        }
+        self.save_cache();
+        self.clear_marks();
+        if pin {
+            self.set_status(format!("Pinned {} song(s) for offline listening, {} downloading", keys.len(), started));
+        } else {
+            self.set_status(format!("Unpinned {} song(s); their offline copies may be evicted", keys.len()));
+        }
+    }
+
+    fn save_cache(&mut self) {
+        if let Err(e) = self.cache.save() {
+            self.set_error(format!("Cannot save cache index: {}", e));
+        }
+    }
+
+    fn poll_cache(&mut self) {
+        let events = self.cache.poll();
+        if events.is_empty() {
+            return;
+        }
+        for event in events {
+            let title = self
+                .playlist
+                .iter()
+                .find(|song| AudioCache::key(song).as_deref() == Some(event.key()))
+                .map(|song| song.title.clone())
+                .unwrap_or_else(|| event.key().to_string());
+            // Cache otomatis cukup di log; yang di-pin dilaporkan ke user
+            let pinned = self.cache.is_pinned(event.key());
+            match event {
+                CacheEvent::Finished { size, .. } => {
+                    log::info!("cached \"{}\" ({} bytes)", title, size);
+                    if pinned {
+                        self.set_status(format!("\"{}\" is available offline", title));
+                    }
+                }
+                CacheEvent::Failed { error, .. } if pinned => {
+                    self.set_error(format!("Offline copy of \"{}\" failed: {}", title, error));
+                }
+                CacheEvent::Failed { error, .. } => log::warn!("caching \"{}\" failed: {}", title, error),
+            }
+        }
+        let playing = self.playing_song.as_ref().and_then(AudioCache::key);
+        let evicted = self.cache.evict(self.config.cache.max_bytes(), playing.as_deref());
+        if !evicted.is_empty() {
+            log::info!("cache over {} MiB, evicted {} file(s)", self.config.cache.max_size_mb, evicted.len());
+        }
+        self.save_cache();
+    }
+
+    fn open_duplicates(&mut self) {
+        self.duplicate_groups = dedupe::find_duplicate_groups(&self.playlist);
+        self.selected_duplicate = 0;
+        if self.duplicate_groups.is_empty() {
+            self.set_status("No duplicates found");
+        } else {
+            self.mp_mode = MusicPlayerMode::Duplicates;
+        }
+    }
+
+    fn handle_duplicates_mode(&mut self, key: KeyEvent) {
+        let count = self.duplicate_groups.len();
         match key.code {
-            KeyCode::Char(' ') => {
-                self.toggle_playback();
-            },
-            KeyCode::Char('n') => {
-                self.next_song();
+            KeyCode::Down if count > 0 => {
+                self.selected_duplicate = (self.selected_duplicate + 1) % count;
             },
-            KeyCode::Char('p') => {
-                self.prev_song();
+            KeyCode::Up if count > 0 => {
+                self.selected_duplicate = (self.selected_duplicate + count - 1) % count;
             },
-            KeyCode::Char('+') => {
-                self.volume = (self.volume + 5).min(100);
+            KeyCode::Enter | KeyCode::Char('y') => {
+                if let Some(group) = self.duplicate_groups.get(self.selected_duplicate).cloned() {
+                    self.merge_duplicates(&[group]);
+                }
             },
-            KeyCode::Char('-') => {
-                self.volume = self.volume.saturating_sub(5);
+            KeyCode::Char('A') => {
+                let groups = std::mem::take(&mut self.duplicate_groups);
+                self.merge_duplicates(&groups);
             },
-            KeyCode::Char('b') => {
-                // Kembali ke mode browse
-                self.mp_mode = MusicPlayerMode::Browse;
+            KeyCode::Char('s') if self.selected_duplicate < count => {
+                // Lewati grup ini tanpa merge
+                self.duplicate_groups.remove(self.selected_duplicate);
             },
-            KeyCode::Esc => {
-                // Stop playback when exiting
-                self.stop_playback();
+            KeyCode::Esc | KeyCode::Char('b') => {
                 self.mp_mode = MusicPlayerMode::Browse;
             },
             _ => {}
         }
+        if self.selected_duplicate >= self.duplicate_groups.len() {
+            self.selected_duplicate = self.duplicate_groups.len().saturating_sub(1);
+        }
+        if self.duplicate_groups.is_empty() {
+            self.mp_mode = MusicPlayerMode::Browse;
+        }
     }
-    
-    fn handle_add_song_mode(&mut self, key: KeyEvent) {
+
+    /// Gabungkan grup duplikat: lagu pertama tiap grup dipertahankan, sisanya dihapus
+    fn merge_duplicates(&mut self, groups: &[Vec<usize>]) {
+        if groups.is_empty() {
+            return;
+        }
+        let removed: usize = groups.iter().map(|group| group.len() - 1).sum();
+        let before = self.snapshot(&[]);
+
+        // Grup yang belum di-merge (dan belum di-skip) diingat lewat kuncinya,
+        // karena index bergeser setelah penghapusan
+        let pending: HashSet<String> = self.duplicate_groups
+            .iter()
+            .filter(|group| !groups.contains(group))
+            .map(|group| dedupe::song_key(&self.playlist[group[0]]))
+            .collect();
+
+        let mut to_remove = Vec::new();
+        for group in groups {
+            let merged = dedupe::merge_group(&self.playlist, group);
+            self.playlist[group[0]] = merged;
+            to_remove.extend_from_slice(&group[1..]);
+        }
+        to_remove.sort_unstable();
+        self.remove_songs(&to_remove);
+        self.commit_edit(format!("merge of {} duplicates", removed), before);
+
+        self.duplicate_groups = dedupe::find_duplicate_groups(&self.playlist)
+            .into_iter()
+            .filter(|group| pending.contains(&dedupe::song_key(&self.playlist[group[0]])))
+            .collect();
+        self.set_status(format!("Merged {} duplicates", removed));
+    }
+
+    // Multi-select: aksi bulk memakai lagu yang ditandai,
+    // atau lagu yang sedang dipilih kalau belum ada yang ditandai
+    fn toggle_mark(&mut self) {
+        if self.playlist.is_empty() {
+            return;
+        }
+        let index = self.selected_song_index;
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+        self.mark_anchor = Some(index);
+    }
+
+    /// Tandai semua lagu antara anchor (mark terakhir) dan lagu terpilih
+    fn mark_range(&mut self) {
+        if self.playlist.is_empty() {
+            return;
+        }
+        let anchor = self.mark_anchor.unwrap_or(self.selected_song_index);
+        let (start, end) = if anchor <= self.selected_song_index {
+            (anchor, self.selected_song_index)
+        } else {
+            (self.selected_song_index, anchor)
+        };
+        self.marked.extend(start..=end.min(self.playlist.len() - 1));
+        self.mark_anchor = Some(self.selected_song_index);
+    }
+
+    fn invert_marks(&mut self) {
+        self.marked = (0..self.playlist.len())
+            .filter(|i| !self.marked.contains(i))
+            .collect();
+    }
+
+    fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+
+    /// Index lagu yang jadi target aksi bulk, urut naik
+    pub fn target_indices(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            if self.playlist.is_empty() {
+                Vec::new()
+            } else {
+                vec![self.selected_song_index]
+            }
+        } else {
+            let mut indices: Vec<usize> = self.marked.iter().copied().collect();
+            indices.sort_unstable();
+            indices
+        }
+    }
+
+    fn target_songs(&self) -> Vec<Song> {
+        self.target_indices()
+            .into_iter()
+            .filter_map(|i| self.playlist.get(i).cloned())
+            .collect()
+    }
+
+    fn describe_targets(&self) -> String {
+        match self.target_indices().as_slice() {
+            [i] => format!("\"{}\"", self.playlist[*i].title),
+            indices => format!("{} songs", indices.len()),
+        }
+    }
+
+    fn enqueue_targets(&mut self) {
+        let songs = self.target_songs();
+        if songs.is_empty() {
+            return;
+        }
+        let description = self.describe_targets();
+        self.queue.extend(songs);
+        self.clear_marks();
+        self.set_status(format!("Queued {}", description));
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        if self.playlist.is_empty() && !matches!(kind, PromptKind::SubscribeFeed | PromptKind::ImportYtdlp) {
+            return;
+        }
+        self.prompt_input = match kind {
+            PromptKind::SubscribeFeed => TextInput::new(),
+            PromptKind::ImportYtdlp => TextInput::with_value("~/"),
+            PromptKind::MoveToPlaylist => TextInput::new(),
+            PromptKind::ExportM3u => TextInput::with_value("~/terminus-export.m3u"),
+            PromptKind::SetArtist => self.shared_target_value(|song| &song.artist),
+            PromptKind::SetTitle => self.shared_target_value(|song| &song.title),
+        };
+        self.prompt_kind = kind;
+        self.mp_mode = MusicPlayerMode::Prompt;
+    }
+
+    /// Pra-isi prompt tag kalau semua lagu target punya nilai yang sama
+    fn shared_target_value(&self, field: impl Fn(&Song) -> &String) -> TextInput {
+        let songs = self.target_songs();
+        let first = field(&songs[0]).clone();
+        if songs.iter().all(|song| *field(song) == first) {
+            TextInput::with_value(first)
+        } else {
+            TextInput::new()
+        }
+    }
+
+    fn handle_prompt_mode(&mut self, key: KeyEvent) {
+        // Kembali ke layar asal prompt
+        let return_mode = match self.prompt_kind {
+            PromptKind::SubscribeFeed => MusicPlayerMode::Podcasts,
+            _ => MusicPlayerMode::Browse,
+        };
         match key.code {
             KeyCode::Esc => {
-                self.mp_mode = MusicPlayerMode::Browse;
+                self.mp_mode = return_mode;
             },
             KeyCode::Enter => {
-                if self.form_field_index < 2 {
-                    // Pindah ke field berikutnya
-                    self.form_field_index += 1;
-                } else {
-                    // Tambahkan lagu baru dan kembali ke mode browse
-                    let new_song = Song {
-                        title: self.form_title.clone(),
-                        artist: self.form_artist.clone(),
-                        url: self.form_url.clone(),
-                        path: None,
-                    };
-                    
-                    self.playlist.push(new_song);
-                    let _ = self.save_playlist(); // Simpan playlist ke disk
-                    self.mp_mode = MusicPlayerMode::Browse;
+                let value = self.prompt_input.value().trim().to_string();
+                if value.is_empty() {
+                    return;
                 }
-            },
-            KeyCode::Tab => {
-                // Pindah ke field berikutnya dengan Tab
-                self.form_field_index = (self.form_field_index + 1) % 3;
-            },
-            KeyCode::BackTab => {
-                // Pindah ke field sebelumnya dengan Shift+Tab
-                if self.form_field_index > 0 {
-                    self.form_field_index -= 1;
-                } else {
-                    self.form_field_index = 2;
+                match self.prompt_kind {
+                    PromptKind::MoveToPlaylist => self.move_targets_to_playlist(&value),
+                    PromptKind::ExportM3u => self.export_targets_m3u(&value),
+                    PromptKind::SetArtist => self.set_targets_artist(&value),
+                    PromptKind::SetTitle => self.set_targets_title(&value),
+                    PromptKind::SubscribeFeed => self.subscribe_feed(value),
+                    PromptKind::ImportYtdlp => {
+                        let result = flat_playlist::read(&expand_tilde(&value));
+                        self.open_import_preview(result);
+                    }
                 }
-            },
-            KeyCode::Backspace => {
-                // Hapus karakter terakhir dari field aktif
-                match self.form_field_index {
-                    0 => {
-                        if !self.form_title.is_empty() {
-                            self.form_title.pop();
-                        }
-                    },
-                    1 => {
-                        if !self.form_artist.is_empty() {
-                            self.form_artist.pop();
-                        }
-                    },
-                    2 => {
-                        if !self.form_url.is_empty() {
-                            self.form_url.pop();
-                        }
-                    },
-                    _ => {}
-                }
-            },
-            KeyCode::Char(c) => {
-                // Tambahkan karakter ke field aktif
-                match self.form_field_index {
-                    0 => self.form_title.push(c),
-                    1 => self.form_artist.push(c),
-                    2 => self.form_url.push(c),
-                    _ => {}
+                // Import membuka layar preview sendiri
+                if self.mp_mode == MusicPlayerMode::Prompt {
+                    self.mp_mode = return_mode;
                 }
             },
-            _ => {}
+            _ => {
+                self.prompt_input.handle_key(key);
+            }
         }
     }
-    
-    fn handle_edit_song_mode(&mut self, key: KeyEvent) {
+
+    // Podcast
+    fn handle_podcasts_mode(&mut self, key: KeyEvent) {
+        let count = self.podcasts.podcasts.len();
         match key.code {
-            KeyCode::Esc => {
-                self.mp_mode = MusicPlayerMode::Browse;
+            KeyCode::Down if count > 0 => {
+                self.selected_podcast = (self.selected_podcast + 1) % count;
             },
-            KeyCode::Enter => {
-                if self.form_field_index < 2 {
-                    // Pindah ke field berikutnya
-                    self.form_field_index += 1;
-                } else {
-                    // Update lagu dan kembali ke mode browse
-                    if let Some(song) = self.playlist.get_mut(self.selected_song_index) {
-                        song.title = self.form_title.clone();
-                        song.artist = self.form_artist.clone();
-                        song.url = self.form_url.clone();
-                    }
-                    
-                    let _ = self.save_playlist(); // Simpan playlist ke disk
-                    self.mp_mode = MusicPlayerMode::Browse;
-                }
+            KeyCode::Up if count > 0 => {
+                self.selected_podcast = (self.selected_podcast + count - 1) % count;
             },
-            KeyCode::Tab => {
-                // Pindah ke field berikutnya dengan Tab
-                self.form_field_index = (self.form_field_index + 1) % 3;
+            KeyCode::Enter if count > 0 => {
+                self.selected_episode = 0;
+                self.mp_mode = MusicPlayerMode::Episodes;
             },
-            KeyCode::BackTab => {
-                // Pindah ke field sebelumnya dengan Shift+Tab
-                if self.form_field_index > 0 {
-                    self.form_field_index -= 1;
-                } else {
-                    self.form_field_index = 2;
+            KeyCode::Char('a') => self.open_prompt(PromptKind::SubscribeFeed),
+            KeyCode::Char('r') => {
+                let urls: Vec<String> = self.podcasts.podcasts.iter().map(|p| p.feed_url.clone()).collect();
+                for url in urls {
+                    self.subscribe_feed(url);
                 }
             },
-            KeyCode::Backspace => {
-                // Hapus karakter terakhir dari field aktif
-                match self.form_field_index {
-                    0 => {
-                        if !self.form_title.is_empty() {
-                            self.form_title.pop();
-                        }
-                    },
-                    1 => {
-                        if !self.form_artist.is_empty() {
-                            self.form_artist.pop();
-                        }
-                    },
-                    2 => {
-                        if !self.form_url.is_empty() {
-                            self.form_url.pop();
-                        }
-                    },
-                    _ => {}
-                }
-            },
-            KeyCode::Char(c) => {
-                // Tambahkan karakter ke field aktif
-                match self.form_field_index {
-                    0 => self.form_title.push(c),
-                    1 => self.form_artist.push(c),
-                    2 => self.form_url.push(c),
-                    _ => {}
-                }
+            KeyCode::Char('d') if count > 0 => {
+                self.mp_mode = MusicPlayerMode::Unsubscribe;
+            },
+            KeyCode::Char('b') | KeyCode::Esc => {
+                self.mp_mode = MusicPlayerMode::Browse;
             },
             _ => {}
         }
     }
-    
-    fn handle_delete_confirm_mode(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('y') => {
-                // Hapus lagu dan kembali ke mode browse
-                if !self.playlist.is_empty() {
-                    self.playlist.remove(self.selected_song_index);
-                    if self.selected_song_index >= self.playlist.len() && !self.playlist.is_empty() {
-                        self.selected_song_index = self.playlist.len() - 1;
-                    }
-                    let _ = self.save_playlist(); // Simpan playlist ke disk
-                }
-                self.mp_mode = MusicPlayerMode::Browse;
-            },
+
+    /// `y` berhenti berlangganan tapi menyimpan file download, `D` ikut
+    /// menghapus file episode yang sudah di-download
+    fn handle_unsubscribe_mode(&mut self, key: KeyEvent) {
+        let delete_files = match key.code {
+            KeyCode::Char('y') => false,
+            KeyCode::Char('D') => true,
             KeyCode::Char('n') | KeyCode::Esc => {
-                // Batal hapus
-                self.mp_mode = MusicPlayerMode::Browse;
+                self.mp_mode = MusicPlayerMode::Podcasts;
+                return;
             },
-            _ => {}
+            _ => return,
+        };
+        self.mp_mode = MusicPlayerMode::Podcasts;
+        if self.selected_podcast >= self.podcasts.podcasts.len() {
+            return;
         }
-    }
-    
-    // Fungsi player kontrol
-     pub fn play_selected_song(&mut self) {
-        // Hentikan jika kosong
-        if self.playlist.is_empty() {
+        let podcast = self.podcasts.podcasts.remove(self.selected_podcast);
+        if self.selected_podcast >= self.podcasts.podcasts.len() {
+            self.selected_podcast = self.podcasts.podcasts.len().saturating_sub(1);
+        }
+        self.save_podcasts();
+        if !delete_files {
+            self.set_status(format!("Unsubscribed from {}", podcast.title));
             return;
         }
-        self.stop_playback();
+        match podcast::remove_downloads(&podcast) {
+            Ok(removed) => {
+                self.set_status(format!("Unsubscribed from {} and deleted {} download(s)", podcast.title, removed));
+            }
+            Err(e) => self.set_error(format!("Unsubscribed from {}, but some downloads could not be deleted: {}", podcast.title, e)),
+        }
+    }
+
+    fn handle_episodes_mode(&mut self, key: KeyEvent) {
+        let Some(podcast) = self.podcasts.podcasts.get(self.selected_podcast) else {
+            self.mp_mode = MusicPlayerMode::Podcasts;
+            return;
+        };
+        let count = podcast.episodes.len();
+        let feed_url = podcast.feed_url.clone();
+        let podcast_title = podcast.title.clone();
+        let episode = podcast.episodes.get(self.selected_episode).cloned();
+
+        match key.code {
+            KeyCode::Down if count > 0 => {
+                self.selected_episode = (self.selected_episode + 1) % count;
+            },
+            KeyCode::Up if count > 0 => {
+                self.selected_episode = (self.selected_episode + count - 1) % count;
+            },
+            KeyCode::Enter => {
+                if let Some(episode) = episode {
+                    self.play_episode(&feed_url, &podcast_title, &episode);
+                }
+            },
+            KeyCode::Char('d') => {
+                let Some(episode) = episode else { return };
+                if episode.download_path.is_some() || self.downloads.contains_key(&episode.guid) {
+                    return;
+                }
+                match podcast::download_dir(&podcast_title) {
+                    Some(dir) => {
+                        self.downloads.insert(episode.guid.clone(), (0, None));
+                        self.set_status(format!("Downloading {}", episode.title));
+                        podcast::spawn_download(feed_url, episode, dir, self.podcast_tx.clone());
+                    }
+                    None => self.set_error("Data directory not found"),
+                }
+            },
+            KeyCode::Char('m') => {
+                let Some(episode) = episode else { return };
+                if let Some(episode) = self.podcasts.episode_mut(&feed_url, &episode.guid) {
+                    episode.played = !episode.played;
+                    episode.position = 0.0;
+                }
+                self.save_podcasts();
+            },
+            KeyCode::Char('b') | KeyCode::Esc => {
+                self.mp_mode = MusicPlayerMode::Podcasts;
+            },
+            _ => {}
+        }
+    }
+
+    fn subscribe_feed(&mut self, url: String) {
+        self.refreshing_feeds += 1;
+        podcast::spawn_fetch(url, self.podcast_tx.clone());
+    }
+
+    /// Putar episode (file download kalau ada) dan lanjutkan dari posisi terakhir
+    fn play_episode(&mut self, feed_url: &str, podcast_title: &str, episode: &podcast::Episode) {
+        let song = Song {
+            title: episode.title.clone(),
+            artist: podcast_title.to_string(),
+            url: episode.enclosure_url.clone(),
+            path: episode.download_path.clone(),
+            radio: false,
+            speed: None,
+            duration: episode.duration,
+        };
+        let start = (!episode.played && episode.position > 5.0).then_some(episode.position);
+        self.play_song_from(song, start);
+        // Episode podcast tidak di-scrobble
+        self.listen_tracker = None;
+        if self.playing_song.is_some() {
+            self.playing_episode = Some((feed_url.to_string(), episode.guid.clone()));
+            self.last_position_poll = Instant::now();
+            self.mp_mode = MusicPlayerMode::NowPlaying;
+        }
+    }
+
+    /// Simpan posisi episode yang sedang diputar (dibaca lewat mpv IPC)
+    fn save_episode_position(&mut self) {
+        let Some((feed_url, guid)) = self.playing_episode.clone() else { return };
+        let Ok(position) = mpv_ipc::get_property("time-pos") else { return };
+        let Some(position) = position.as_f64() else { return };
+        if let Some(episode) = self.podcasts.episode_mut(&feed_url, &guid) {
+            episode.position = position;
+            // Anggap selesai kalau sudah lewat 95% durasi
+            if episode.duration.is_some_and(|d| position >= d as f64 * 0.95) {
+                episode.played = true;
+                episode.position = 0.0;
+            }
+        }
+        self.save_podcasts();
+    }
+
+    fn finish_episode(&mut self) {
+        let Some((feed_url, guid)) = self.playing_episode.take() else { return };
+        if let Some(episode) = self.podcasts.episode_mut(&feed_url, &guid) {
+            episode.played = true;
+            episode.position = 0.0;
+        }
+        self.save_podcasts();
+    }
+
+    fn save_podcasts(&mut self) {
+        if let Err(e) = self.podcasts.save() {
+            self.set_error(format!("Cannot save podcasts: {}", e));
+        }
+    }
+
+    fn poll_podcasts(&mut self) {
+        let events: Vec<PodcastEvent> = self.podcast_rx.try_iter().collect();
+        for event in events {
+            match event {
+                PodcastEvent::FeedLoaded(Ok(podcast)) => {
+                    self.refreshing_feeds = self.refreshing_feeds.saturating_sub(1);
+                    self.set_status(format!("Updated {} ({} episodes)", podcast.title, podcast.episodes.len()));
+                    self.podcasts.upsert(podcast);
+                    self.save_podcasts();
+                }
+                PodcastEvent::FeedLoaded(Err(error)) => {
+                    self.refreshing_feeds = self.refreshing_feeds.saturating_sub(1);
+                    self.set_error(format!("Feed error: {}", error));
+                }
+                PodcastEvent::DownloadProgress { guid, downloaded, total } => {
+                    self.downloads.insert(guid, (downloaded, total));
+                }
+                PodcastEvent::DownloadFinished { feed_url, guid, path } => {
+                    self.downloads.remove(&guid);
+                    if let Some(episode) = self.podcasts.episode_mut(&feed_url, &guid) {
+                        episode.download_path = Some(path.to_string_lossy().into_owned());
+                        let title = episode.title.clone();
+                        self.set_status(format!("Downloaded {}", title));
+                    }
+                    self.save_podcasts();
+                }
+                PodcastEvent::DownloadFailed { guid, error } => {
+                    self.downloads.remove(&guid);
+                    self.set_error(format!("Download failed: {}", error));
+                }
+            }
+        }
+    }
+
+    /// Baca ulang `playlist.json` setelah diubah dari luar. Perubahan baru
+    /// diterapkan di mode yang tidak memegang index lagu (bukan form edit,
+    /// konfirmasi hapus, prompt, atau daftar duplikat).
+    fn poll_playlist_file(&mut self) {
+        let Some(watcher) = &self.playlist_watcher else { return };
+        if watcher.changed() {
+            self.reload_due = Some(Instant::now() + RELOAD_DEBOUNCE);
+        }
+        if let Some(at) = self.reload_due
+            && Instant::now() >= at
+        {
+            self.reload_due = None;
+            self.read_external_playlist();
+        }
+
+        let idle = matches!(
+            self.mp_mode,
+            MusicPlayerMode::Browse
+                | MusicPlayerMode::NowPlaying
+                | MusicPlayerMode::Podcasts
+                | MusicPlayerMode::Unsubscribe
+                | MusicPlayerMode::Episodes
+        );
+        if !idle || self.pending_reload.is_none() {
+            return;
+        }
+        if self.playlist == self.disk_playlist {
+            if let Some(songs) = self.pending_reload.take() {
+                self.apply_reload(songs);
+            }
+        } else if self.state == AppState::MusicPlayer {
+            self.mp_mode = MusicPlayerMode::ReloadConflict;
+        }
+    }
+
+    fn read_external_playlist(&mut self) {
+        let Some(path) = Self::playlist_path() else { return };
+        match schema::read_songs(&path) {
+            // File sedang diganti atau dihapus; simpan berikutnya akan membuatnya lagi
+            Ok(None) => {}
+            // Tulisan sendiri, atau isinya dikembalikan seperti semula
+            Ok(Some(songs)) if songs == self.disk_playlist => self.pending_reload = None,
+            Ok(Some(songs)) => self.pending_reload = Some(songs),
+            Err(e) => self.set_warning(format!("playlist.json changed but cannot be read: {}", e)),
+        }
+    }
+
+    /// Pakai isi `playlist.json`; playlist sebelumnya bisa dikembalikan dengan undo
+    fn apply_reload(&mut self, songs: Vec<Song>) {
+        // Isinya sudah sama dengan disk, jadi tidak ada yang perlu disimpan
+        let before = self.snapshot(&[]);
+        self.history.record("reload of playlist.json", before);
+        self.disk_playlist = songs.clone();
+        self.replace_playlist(songs);
+        self.set_status(format!("Reloaded playlist.json ({} songs)", self.playlist.len()));
+    }
+
+    /// Ganti seluruh playlist sambil mempertahankan lagu terpilih, tanda,
+    /// dan info lagu yang sedang diputar kalau lagunya masih ada
+    fn replace_playlist(&mut self, songs: Vec<Song>) {
+        let selected = self.playlist.get(self.selected_song_index).map(dedupe::song_key);
+        let marked: HashSet<String> = self
+            .marked
+            .iter()
+            .filter_map(|&i| self.playlist.get(i))
+            .map(dedupe::song_key)
+            .collect();
+
+        self.playlist = songs;
+        let keys: Vec<String> = self.playlist.iter().map(dedupe::song_key).collect();
+        self.marked = (0..keys.len()).filter(|&i| marked.contains(&keys[i])).collect();
+        self.mark_anchor = None;
+        self.selected_song_index = selected
+            .and_then(|key| keys.iter().position(|k| *k == key))
+            .unwrap_or(self.selected_song_index)
+            .min(self.playlist.len().saturating_sub(1));
+
+        if let Some(playing) = &self.playing_song {
+            let key = dedupe::song_key(playing);
+            if let Some(i) = keys.iter().position(|k| *k == key) {
+                self.playing_song = Some(self.playlist[i].clone());
+            }
+        }
+    }
+
+    fn move_targets_to_playlist(&mut self, name: &str) {
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            self.set_warning(format!("Invalid playlist name: {}", name));
+            return;
+        }
+        let description = format!("move of {} to {}", self.describe_targets(), name);
+        let indices = self.target_indices();
+        let mut target = match Self::load_named_playlist(name) {
+            Ok(songs) => songs,
+            Err(e) => {
+                self.set_error(format!("Cannot read playlist {}: {}", name, e));
+                return;
+            }
+        };
+
+        // Snapshot diambil sebelum menulis, tapi baru masuk history kalau
+        // penyimpanannya berhasil; undo untuk save yang gagal tidak berarti apa-apa
+        let snapshot = self.snapshot(&[name.to_string()]);
+        target.extend(indices.iter().map(|&i| self.playlist[i].clone()));
+        if let Err(e) = Self::save_named_playlist(name, &target) {
+            self.set_error(format!("Failed to save playlist {}: {}", name, e));
+            return;
+        }
+        self.history.record(description, snapshot);
+        self.remove_songs(&indices);
+        self.persist_playlist();
+        self.set_status(format!("Moved {} songs to {}", indices.len(), name));
+    }
+
+    /// Tampilkan isi dump yt-dlp untuk dipilih sebelum di-import
+    pub fn open_import_preview(&mut self, result: Result<FlatPlaylist, String>) {
+        let playlist = match result {
+            Ok(playlist) => playlist,
+            Err(e) => {
+                self.set_error(format!("Import failed: {}", e));
+                return;
+            }
+        };
+        log::info!("import preview: {} entries", playlist.entries.len());
+        let count = playlist.entries.len();
+        self.import = Some(ImportPreview {
+            title: playlist.title,
+            selected: playlist.entries.iter().map(|entry| entry.available).collect(),
+            entries: playlist.entries,
+            existing: vec![false; count],
+            cursor: 0,
+            target: TextInput::new(),
+            editing_target: false,
+        });
+        self.refresh_import_existing();
+        self.state = AppState::MusicPlayer;
+        self.mp_mode = MusicPlayerMode::ImportPreview;
+    }
+
+    /// Hitung ulang entri yang sudah ada di playlist tujuan; entri itu
+    /// tidak dipilih lagi, pilihan user yang lain tetap
+    fn refresh_import_existing(&mut self) {
+        let Some(preview) = &self.import else { return };
+        let name = preview.target.value().trim().to_string();
+        let target = if name.is_empty() {
+            self.playlist.clone()
+        } else {
+            match Self::load_named_playlist(&name) {
+                Ok(songs) => songs,
+                Err(e) => {
+                    self.set_error(format!("Cannot read playlist {}: {}", name, e));
+                    Vec::new()
+                }
+            }
+        };
+        let Some(preview) = &mut self.import else { return };
+        for (i, entry) in preview.entries.iter().enumerate() {
+            let existing = dedupe::find_duplicate_of(&target, &entry.song, None).is_some();
+            preview.existing[i] = existing;
+            if existing {
+                preview.selected[i] = false;
+            }
+        }
+    }
+
+    fn handle_import_preview_mode(&mut self, key: KeyEvent) {
+        let Some(preview) = &mut self.import else {
+            self.mp_mode = MusicPlayerMode::Browse;
+            return;
+        };
+        if preview.editing_target {
+            match key.code {
+                KeyCode::Esc => preview.editing_target = false,
+                KeyCode::Enter => {
+                    preview.editing_target = false;
+                    self.refresh_import_existing();
+                }
+                _ => {
+                    preview.target.handle_key(key);
+                }
+            }
+            return;
+        }
+        let count = preview.entries.len();
+        match key.code {
+            KeyCode::Down => preview.cursor = (preview.cursor + 1) % count,
+            KeyCode::Up => preview.cursor = (preview.cursor + count - 1) % count,
+            KeyCode::PageDown => preview.cursor = (preview.cursor + 10).min(count - 1),
+            KeyCode::PageUp => preview.cursor = preview.cursor.saturating_sub(10),
+            KeyCode::Home => preview.cursor = 0,
+            KeyCode::End => preview.cursor = count - 1,
+            KeyCode::Char(' ') => {
+                preview.selected[preview.cursor] = !preview.selected[preview.cursor];
+                preview.cursor = (preview.cursor + 1).min(count - 1);
+            }
+            KeyCode::Char('a') => {
+                // Pilih semua, atau kosongkan kalau semua sudah terpilih
+                let all = preview.selected.iter().all(|selected| *selected);
+                preview.selected.iter_mut().for_each(|selected| *selected = !all);
+            }
+            KeyCode::Char('t') => preview.editing_target = true,
+            KeyCode::Enter => self.import_selected(),
+            KeyCode::Char('b') | KeyCode::Esc => {
+                self.import = None;
+                self.mp_mode = MusicPlayerMode::Browse;
+            }
+            _ => {}
+        }
+    }
+
+    /// Tambahkan entri terpilih ke playlist tujuan; yang sudah ada dilewati
+    fn import_selected(&mut self) {
+        let Some(preview) = &self.import else { return };
+        let name = preview.target.value().trim().to_string();
+        let songs: Vec<Song> = preview
+            .entries
+            .iter()
+            .zip(&preview.selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(entry, _)| entry.song.clone())
+            .collect();
+        if songs.is_empty() {
+            self.set_warning("Nothing selected");
+            return;
+        }
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            self.set_warning(format!("Invalid playlist name: {}", name));
+            return;
+        }
+
+        let total = songs.len();
+        let added = if name.is_empty() {
+            let before = self.snapshot(&[]);
+            let added = append_new(&mut self.playlist, songs);
+            self.commit_edit(format!("import of {} songs", total), before);
+            added
+        } else {
+            let mut target = match Self::load_named_playlist(&name) {
+                Ok(songs) => songs,
+                Err(e) => {
+                    self.set_error(format!("Cannot read playlist {}: {}", name, e));
+                    return;
+                }
+            };
+            let snapshot = self.snapshot(std::slice::from_ref(&name));
+            let added = append_new(&mut target, songs);
+            if let Err(e) = Self::save_named_playlist(&name, &target) {
+                self.set_error(format!("Failed to save playlist {}: {}", name, e));
+                return;
+            }
+            self.history.record(format!("import of {} songs to {}", total, name), snapshot);
+            added
+        };
+
+        let destination = if name.is_empty() { String::from("the playlist") } else { name };
+        self.set_status(format!(
+            "Imported {} songs into {} ({} duplicates skipped)",
+            added,
+            destination,
+            total - added
+        ));
+        self.import = None;
+        self.mp_mode = MusicPlayerMode::Browse;
+    }
+
+    fn export_targets_m3u(&mut self, path: &str) {
+        let songs = self.target_songs();
+        let path = expand_tilde(path);
+        match write_m3u(&path, &songs) {
+            Ok(()) => {
+                self.clear_marks();
+                self.set_status(format!("Exported {} songs to {}", songs.len(), path.display()));
+            }
+            Err(e) => self.set_error(format!("Export failed: {}", e)),
+        }
+    }
+
+    fn set_targets_artist(&mut self, artist: &str) {
+        let description = format!("retag of {}", self.describe_targets());
+        let indices = self.target_indices();
+        let before = self.snapshot(&[]);
+        for i in indices {
+            self.playlist[i].artist = artist.to_string();
+        }
+        self.clear_marks();
+        self.commit_edit(description, before);
+    }
+
+    fn set_targets_title(&mut self, title: &str) {
+        let description = format!("retitle of {}", self.describe_targets());
+        let indices = self.target_indices();
+        let before = self.snapshot(&[]);
+        for i in indices {
+            self.playlist[i].title = title.to_string();
+        }
+        self.clear_marks();
+        self.commit_edit(description, before);
+    }
+
+    /// Hapus lagu berdasarkan index dan rapikan selection
+    fn remove_songs(&mut self, indices: &[usize]) {
+        for &i in indices.iter().rev() {
+            if i < self.playlist.len() {
+                self.playlist.remove(i);
+            }
+        }
+        self.clear_marks();
+        if self.selected_song_index >= self.playlist.len() {
+            self.selected_song_index = self.playlist.len().saturating_sub(1);
+        }
+    }
+
+    /// Geser lagu terpilih ke atas (-1) atau ke bawah (+1)
+    fn move_selected_song(&mut self, offset: isize) {
+        let from = self.selected_song_index;
+        let to = from as isize + offset;
+        if to < 0 || to as usize >= self.playlist.len() {
+            return;
+        }
+        let to = to as usize;
+        let title = self.playlist[from].title.clone();
+        let before = self.snapshot(&[]);
+        self.playlist.swap(from, to);
+        let (from_marked, to_marked) = (self.marked.remove(&from), self.marked.remove(&to));
+        if from_marked {
+            self.marked.insert(to);
+        }
+        if to_marked {
+            self.marked.insert(from);
+        }
+        self.selected_song_index = to;
+        self.commit_edit(format!("move of \"{}\"", title), before);
+    }
+
+    /// Simpan playlist yang sudah diubah, lalu catat `before` supaya bisa di-undo.
+    /// History hanya bertambah kalau penyimpanannya berhasil.
+    fn commit_edit(&mut self, description: impl Into<String>, before: PlaylistSnapshot) {
+        if self.persist_playlist() {
+            self.history.record(description, before);
+        }
+    }
+
+    fn snapshot(&self, others: &[String]) -> PlaylistSnapshot {
+        PlaylistSnapshot {
+            songs: self.playlist.clone(),
+            others: others
+                .iter()
+                .map(|name| (name.clone(), Self::load_named_playlist(name).unwrap_or_default()))
+                .collect(),
+        }
+    }
+
+    pub fn undo(&mut self) {
+        let others = match self.history.peek_undo() {
+            Some(snapshot) => snapshot.other_names(),
+            None => return self.set_status("Nothing to undo"),
+        };
+        let current = self.snapshot(&others);
+        if let Some((description, snapshot)) = self.history.undo(current) {
+            self.restore_snapshot(snapshot);
+            self.set_status(format!("Undid {}", description));
+        }
+    }
+
+    pub fn redo(&mut self) {
+        let others = match self.history.peek_redo() {
+            Some(snapshot) => snapshot.other_names(),
+            None => return self.set_status("Nothing to redo"),
+        };
+        let current = self.snapshot(&others);
+        if let Some((description, snapshot)) = self.history.redo(current) {
+            self.restore_snapshot(snapshot);
+            self.set_status(format!("Redid {}", description));
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: PlaylistSnapshot) {
+        self.playlist = snapshot.songs;
+        self.clear_marks();
+        if self.selected_song_index >= self.playlist.len() {
+            self.selected_song_index = self.playlist.len().saturating_sub(1);
+        }
+        for (name, songs) in &snapshot.others {
+            if let Err(e) = Self::save_named_playlist(name, songs) {
+                self.set_error(format!("Cannot save playlist \"{}\": {}", name, e));
+            }
+        }
+        self.persist_playlist();
+    }
+
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.toasts.push(Level::Info, message);
+    }
+
+    pub fn set_warning(&mut self, message: impl Into<String>) {
+        self.toasts.push(Level::Warn, message);
+    }
+
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        self.toasts.push(Level::Error, message);
+    }
+
+    /// Pesan toast yang masih tampil
+    pub fn current_status(&self) -> Option<&str> {
+        self.toasts.current().map(|toast| toast.message.as_str())
+    }
+
+    /// Buka scrollback semua pesan; Esc kembali ke layar sebelumnya
+    pub fn open_messages(&mut self) {
+        if self.state != AppState::Messages {
+            self.messages_return = self.state;
+        }
+        self.state = AppState::Messages;
+        self.selected_message = 0;
+    }
+
+    pub fn open_logs(&mut self) {
+        self.state = AppState::Logs;
+        self.log_view.invalidate();
+        self.log_view.follow = true;
+        self.log_view.refresh();
+    }
+
+    pub fn handle_logs_input(&mut self, key: KeyEvent) {
+        let view = &mut self.log_view;
+        if view.searching {
+            match key.code {
+                KeyCode::Enter => view.searching = false,
+                KeyCode::Esc => {
+                    view.search.clear();
+                    view.searching = false;
+                }
+                _ => {
+                    view.search.handle_key(key);
+                }
+            }
+            view.clamp_selection();
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::MainMenu,
+            KeyCode::Up => view.scroll(-1),
+            KeyCode::Down => view.scroll(1),
+            KeyCode::PageUp => view.scroll(-20),
+            KeyCode::PageDown => view.scroll(20),
+            KeyCode::Home => {
+                view.follow = false;
+                view.selected = 0;
+            }
+            KeyCode::End => {
+                view.follow = true;
+                view.clamp_selection();
+            }
+            KeyCode::Char('f') => {
+                view.follow = !view.follow;
+                view.clamp_selection();
+            }
+            KeyCode::Char('l') => view.cycle_level(),
+            KeyCode::Char('/') => view.searching = true,
+            KeyCode::Char('r') => {
+                view.invalidate();
+                view.refresh();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn open_settings(&mut self) {
+        if self.state != AppState::Settings {
+            self.settings_return = self.state;
+        }
+        self.state = AppState::Settings;
+        self.scan_audio_devices();
+    }
+
+    fn scan_audio_devices(&mut self) {
+        self.device_error = None;
+        self.device_scan = Some(audio_device::spawn_scan(self.player_process.is_some()));
+    }
+
+    /// Device yang dipakai sekarang menurut config
+    pub fn active_audio_device(&self) -> &str {
+        self.config.audio_device.as_deref().unwrap_or(audio_device::AUTO)
+    }
+
+    fn poll_device_scan(&mut self) {
+        let Some(scan) = &self.device_scan else { return };
+        let result = match scan.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(String::from("device scan stopped unexpectedly")),
+        };
+        self.device_scan = None;
+        match result {
+            Ok(mut devices) => {
+                // Device tersimpan yang sedang tidak terpasang (mis. HDMI dicabut) tetap ditampilkan
+                let active = self.active_audio_device().to_string();
+                if !devices.iter().any(|device| device.name == active) {
+                    devices.push(AudioDevice { name: active.clone(), description: String::from("not connected") });
+                }
+                self.selected_device = devices.iter().position(|device| device.name == active).unwrap_or(0);
+                self.audio_devices = devices;
+            }
+            Err(e) => self.device_error = Some(e),
+        }
+    }
+
+    /// Pakai device terpilih: simpan ke config dan pindahkan mpv yang sedang jalan
+    fn select_audio_device(&mut self) {
+        let Some(device) = self.audio_devices.get(self.selected_device).cloned() else { return };
+        self.config.audio_device = (device.name != audio_device::AUTO).then(|| device.name.clone());
+        if let Err(e) = self.config.save() {
+            self.set_error(format!("Cannot save config: {}", e));
+        }
+        let label = if device.description.is_empty() { device.name.clone() } else { device.description.clone() };
+        // mpv bisa ganti output tanpa mengulang lagu
+        if self.player_process.is_some()
+            && let Err(e) = mpv_ipc::set_property("audio-device", json!(device.name))
+        {
+            self.set_error(format!("Cannot switch to {}: {}", label, e));
+            return;
+        }
+        log::info!("audio output set to {}", device.name);
+        self.set_status(format!("Audio output: {}", label));
+    }
+
+    pub fn handle_settings_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = self.settings_return,
+            KeyCode::Down if self.selected_device + 1 < self.audio_devices.len() => self.selected_device += 1,
+            KeyCode::Up => self.selected_device = self.selected_device.saturating_sub(1),
+            KeyCode::Enter => self.select_audio_device(),
+            KeyCode::Char('r') => self.scan_audio_devices(),
+            KeyCode::Char('!') => self.open_messages(),
+            _ => {}
+        }
+    }
+
+    pub fn handle_downloads_input(&mut self, key: KeyEvent) {
+        if let Some(prompt) = self.download_prompt {
+            match key.code {
+                KeyCode::Esc => self.download_prompt = None,
+                KeyCode::Enter => {
+                    let value = self.download_input.value().trim().to_string();
+                    match prompt {
+                        DownloadPrompt::Url => self.queue_downloads(&value),
+                        DownloadPrompt::Playlist => self.add_download_to_playlist(&value),
+                    }
+                }
+                _ => {
+                    self.download_input.handle_key(key);
+                }
+            }
+            return;
+        }
+
+        let count = self.downloader.jobs.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = AppState::MainMenu,
+            KeyCode::Char('!') => self.open_messages(),
+            KeyCode::Down if self.selected_job + 1 < count => self.selected_job += 1,
+            KeyCode::Up => self.selected_job = self.selected_job.saturating_sub(1),
+            KeyCode::Char('a') => {
+                self.download_input.clear();
+                self.download_prompt = Some(DownloadPrompt::Url);
+            }
+            KeyCode::Char('f') => {
+                self.download_preset = self.download_preset.next();
+                self.config.downloads.preset = self.download_preset;
+                if let Err(e) = self.config.save() {
+                    self.set_error(format!("Cannot save config: {}", e));
+                }
+            }
+            KeyCode::Char('c') => {
+                if self.downloader.cancel(self.selected_job) {
+                    self.set_status("Download cancelled");
+                } else if count > 0 {
+                    self.set_warning("Only queued or running downloads can be cancelled");
+                }
+            }
+            KeyCode::Char('r') => {
+                if self.downloader.retry(self.selected_job) {
+                    self.set_status("Download queued again");
+                } else if count > 0 {
+                    self.set_warning("Only failed or cancelled downloads can be retried");
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if self.downloader.remove(self.selected_job) {
+                    self.selected_job = self.selected_job.min(self.downloader.jobs.len().saturating_sub(1));
+                } else if count > 0 {
+                    self.set_warning("Cancel the download before removing it");
+                }
+            }
+            KeyCode::Char('p') => match self.downloader.jobs.get(self.selected_job).map(|job| &job.state) {
+                Some(JobState::Finished(_)) => {
+                    self.download_input.clear();
+                    self.download_prompt = Some(DownloadPrompt::Playlist);
+                }
+                Some(_) => self.set_warning("Only finished downloads can be added to a playlist"),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Masukkan satu atau beberapa URL (dipisah spasi, mis. hasil paste) ke antrean
+    fn queue_downloads(&mut self, input: &str) {
+        let mut queued = 0;
+        for url in input.split_whitespace() {
+            match SongSource::parse(url) {
+                Ok(source) if !source.is_local() => {
+                    self.downloader.add(url.to_string(), self.download_preset);
+                    queued += 1;
+                }
+                Ok(_) => {
+                    self.set_warning(format!("Not a URL: {}", url));
+                    return;
+                }
+                Err(e) => {
+                    self.set_warning(format!("Invalid URL: {}", e));
+                    return;
+                }
+            }
+        }
+        if queued == 0 {
+            return;
+        }
+        self.download_prompt = None;
+        self.selected_job = self.downloader.jobs.len() - 1;
+        self.set_status(format!("Queued {} download(s) as {}", queued, self.download_preset.label()));
+    }
+
+    fn poll_downloads(&mut self) {
+        let dir = self.config.downloads.dir();
+        for notice in self.downloader.poll(&dir, self.config.downloads.concurrency) {
+            match notice {
+                Notice::Finished { title } => self.set_status(format!("Downloaded \"{}\"", title)),
+                Notice::Failed { title, error } => {
+                    self.set_error(format!("Download of \"{}\" failed: {}", title, error));
+                }
+            }
+        }
+    }
+
+    /// Tambahkan hasil download terpilih ke playlist utama (`name` kosong) atau playlist bernama
+    fn add_download_to_playlist(&mut self, name: &str) {
+        let Some(job) = self.downloader.jobs.get(self.selected_job) else { return };
+        let JobState::Finished(path) = &job.state else { return };
+        let title = job.title.clone().unwrap_or_else(|| {
+            path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+        });
+        let song = Song {
+            title: title.clone(),
+            artist: job.artist.clone().unwrap_or_default(),
+            url: String::new(),
+            path: Some(path.to_string_lossy().into_owned()),
+            radio: false,
+            speed: None,
+            duration: None,
+        };
+
+        if name.is_empty() {
+            if let Some(existing) = dedupe::find_duplicate_of(&self.playlist, &song, None) {
+                self.set_warning(format!("Already in the playlist as #{}", existing + 1));
+                return;
+            }
+            let before = self.snapshot(&[]);
+            self.playlist.push(song);
+            self.commit_edit(format!("add of \"{}\"", title), before);
+            self.set_status(format!("Added \"{}\" to the playlist", title));
+        } else {
+            if name.contains(['/', '\\']) || name.starts_with('.') {
+                self.set_warning(format!("Invalid playlist name: {}", name));
+                return;
+            }
+            let mut target = match Self::load_named_playlist(name) {
+                Ok(songs) => songs,
+                Err(e) => {
+                    self.set_error(format!("Cannot read playlist {}: {}", name, e));
+                    return;
+                }
+            };
+            let snapshot = self.snapshot(&[name.to_string()]);
+            target.push(song);
+            if let Err(e) = Self::save_named_playlist(name, &target) {
+                self.set_error(format!("Failed to save playlist {}: {}", name, e));
+                return;
+            }
+            self.history.record(format!("add of \"{}\" to {}", title, name), snapshot);
+            self.set_status(format!("Added \"{}\" to {}", title, name));
+        }
+        self.download_prompt = None;
+    }
+
+    pub fn handle_messages_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.state = self.messages_return,
+            KeyCode::Down if self.selected_message + 1 < self.toasts.len() => self.selected_message += 1,
+            KeyCode::Up => self.selected_message = self.selected_message.saturating_sub(1),
+            KeyCode::Home => self.selected_message = 0,
+            KeyCode::End => self.selected_message = self.toasts.len().saturating_sub(1),
+            KeyCode::Char('c') => {
+                self.toasts.clear();
+                self.selected_message = 0;
+            }
+            _ => {}
+        }
+    }
+    
+    fn handle_now_playing_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('!') => self.open_messages(),
+            KeyCode::Char('i') => self.open_diagnostics(),
+            KeyCode::Char(' ') => {
+                self.toggle_playback();
+            },
+            KeyCode::Char('n') => {
+                self.next_song();
+            },
+            KeyCode::Char('p') => {
+                self.prev_song();
+            },
+            KeyCode::Char('+') => {
+                self.set_volume(self.volume.saturating_add(5));
+            },
+            KeyCode::Char('-') => {
+                self.set_volume(self.volume.saturating_sub(5));
+            },
+            KeyCode::Char('N') => {
+                self.toggle_notifications();
+            },
+            KeyCode::Char('[') => self.set_speed(self.speed - SPEED_STEP),
+            KeyCode::Char(']') => self.set_speed(self.speed + SPEED_STEP),
+            KeyCode::Char('=') => self.set_speed(1.0),
+            KeyCode::Char('l') => self.cycle_ab_loop(),
+            KeyCode::Char('o') => self.open_settings(),
+            KeyCode::Char('b') => {
+                // Kembali ke mode browse
+                self.mp_mode = MusicPlayerMode::Browse;
+            },
+            KeyCode::Esc => {
+                // Stop playback when exiting
+                self.stop_playback();
+                self.mp_mode = MusicPlayerMode::Browse;
+            },
+            _ => {}
+        }
+    }
+    
+    /// Input untuk form Add Song dan Edit Song (keduanya memakai field yang sama)
+    fn handle_song_form_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.mp_mode = MusicPlayerMode::Browse;
+            },
+            KeyCode::Enter => {
+                if self.form_field_index < 2 {
+                    // Pindah ke field berikutnya
+                    self.form_field_index += 1;
+                } else {
+                    self.submit_song_form();
+                }
+            },
+            KeyCode::Tab => {
+                // Pindah ke field berikutnya dengan Tab
+                self.form_field_index = (self.form_field_index + 1) % 3;
+            },
+            KeyCode::BackTab => {
+                // Pindah ke field sebelumnya dengan Shift+Tab
+                if self.form_field_index > 0 {
+                    self.form_field_index -= 1;
+                } else {
+                    self.form_field_index = 2;
+                }
+            },
+            _ => {
+                let before = self.form_field_value().to_string();
+                if let Some(input) = self.active_text_input_mut() {
+                    input.handle_key(key);
+                }
+                if self.form_field_value() != before {
+                    self.song_form_edited();
+                }
+            }
+        }
+    }
+
+    fn form_field_value(&self) -> &str {
+        match self.form_field_index {
+            0 => self.form_title.value(),
+            1 => self.form_artist.value(),
+            _ => self.form_url.value(),
+        }
+    }
+
+    fn reset_form_metadata(&mut self, touched: [bool; 2], duration: Option<u64>) {
+        self.form_touched = touched;
+        self.form_duration = duration;
+        self.metadata_lookup = None;
+        self.metadata_due = None;
+    }
+
+    /// Field form yang fokus baru diubah user (ketik atau paste)
+    fn song_form_edited(&mut self) {
+        match self.form_field_index {
+            index @ (0 | 1) => self.form_touched[index] = true,
+            _ => {
+                // Metadata lama milik sumber lama; cari ulang setelah user berhenti mengetik
+                self.form_duration = None;
+                self.metadata_lookup = None;
+                self.metadata_due = Some(Instant::now() + METADATA_DELAY);
+            }
+        }
+    }
+
+    /// Cari cover lagu yang tampil di NowPlaying. Hanya file lokal yang punya
+    /// cover (tertanam atau gambar di foldernya); lagu URL dan radio tidak.
+    fn update_album_art(&mut self) {
+        if self.state != AppState::MusicPlayer || self.mp_mode != MusicPlayerMode::NowPlaying {
+            return;
+        }
+        let song = self.playing_song.as_ref().or(self.playlist.get(self.selected_song_index));
+        let path = match song.filter(|song| !song.radio).map(SongSource::of) {
+            Some(Ok(SongSource::LocalFile(path))) => Some(path),
+            _ => None,
+        };
+        self.album_art.show(path);
+        self.album_art.poll();
+    }
+
+    /// Mulai lookup yang sudah jatuh tempo dan isi form dengan hasilnya.
+    /// Hanya Title/Artist yang belum diubah user yang diisi.
+    fn poll_metadata(&mut self) {
+        let in_form = self.state == AppState::MusicPlayer
+            && matches!(self.mp_mode, MusicPlayerMode::AddSong | MusicPlayerMode::EditSong);
+        if !in_form {
+            self.metadata_lookup = None;
+            self.metadata_due = None;
+            return;
+        }
+        if self.metadata_due.is_some_and(|due| Instant::now() >= due) {
+            self.metadata_due = None;
+            self.metadata_lookup = Lookup::start(self.form_url.value().trim());
+        }
+
+        let Some(result) = self.metadata_lookup.as_ref().and_then(Lookup::result) else { return };
+        let Some(lookup) = self.metadata_lookup.take() else { return };
+        if lookup.input != self.form_url.value().trim() {
+            return;
+        }
+        match result {
+            Ok(metadata) => {
+                if let Some(title) = metadata.title.filter(|_| !self.form_touched[0]) {
+                    self.form_title = TextInput::with_value(title);
+                }
+                if let Some(artist) = metadata.artist.filter(|_| !self.form_touched[1]) {
+                    self.form_artist = TextInput::with_value(artist);
+                }
+                self.form_duration = metadata.duration;
+            }
+            Err(e) => {
+                log::info!("metadata lookup for {} failed: {}", lookup.input, e);
+                self.set_warning(format!("Could not read metadata: {}", e));
+            }
+        }
+    }
+
+    fn submit_song_form(&mut self) {
+        // Validasi sumber dulu
+        let candidate = match Song::from_input(self.form_title.value(), self.form_artist.value(), self.form_url.value()) {
+            Ok(song) => Song { duration: self.form_duration, ..song },
+            Err(e) => {
+                self.set_warning(format!("Invalid source: {}", e));
+                self.form_field_index = 2;
+                return;
+            }
+        };
+
+        // Peringatkan sekali kalau lagu ini sudah ada; Enter kedua tetap menyimpan
+        let skip = (self.mp_mode == MusicPlayerMode::EditSong).then_some(self.selected_song_index);
+        if let Some(existing) = dedupe::find_duplicate_of(&self.playlist, &candidate, skip) {
+            let key = dedupe::song_key(&candidate);
+            if self.form_duplicate_ack.as_deref() != Some(key.as_str()) {
+                self.set_warning(format!(
+                    "Duplicate of \"{}\" (#{}). Press Enter again to save anyway.",
+                    self.playlist[existing].title,
+                    existing + 1
+                ));
+                self.form_duplicate_ack = Some(key);
+                return;
+            }
+        }
+
+        let before = self.snapshot(&[]);
+        let description = if self.mp_mode == MusicPlayerMode::EditSong {
+            // Update lagu yang sedang dipilih
+            let Some(song) = self.playlist.get_mut(self.selected_song_index) else {
+                self.mp_mode = MusicPlayerMode::Browse;
+                return;
+            };
+            let description = format!("edit of \"{}\"", song.title);
+            self.source_errors.remove(&song_source(song));
+            song.title = candidate.title;
+            song.artist = candidate.artist;
+            song.url = candidate.url;
+            song.path = candidate.path;
+            song.radio |= candidate.radio;
+            song.duration = candidate.duration;
+            description
+        } else {
+            // Tambahkan lagu baru
+            let description = format!("add of \"{}\"", candidate.title);
+            self.playlist.push(candidate);
+            description
+        };
+
+        self.commit_edit(description, before); // Simpan playlist ke disk
+        self.mp_mode = MusicPlayerMode::Browse;
+    }
+
+    /// Text input yang sedang fokus, kalau layar aktif punya form
+    pub fn active_text_input_mut(&mut self) -> Option<&mut TextInput> {
+        if self.state == AppState::Logs && self.log_view.searching {
+            return Some(&mut self.log_view.search);
+        }
+        if self.state == AppState::Downloads && self.download_prompt.is_some() {
+            return Some(&mut self.download_input);
+        }
+        if self.state != AppState::MusicPlayer {
+            return None;
+        }
+        match self.mp_mode {
+            MusicPlayerMode::AddSong | MusicPlayerMode::EditSong => match self.form_field_index {
+                0 => Some(&mut self.form_title),
+                1 => Some(&mut self.form_artist),
+                2 => Some(&mut self.form_url),
+                _ => None,
+            },
+            MusicPlayerMode::Prompt => Some(&mut self.prompt_input),
+            MusicPlayerMode::ImportPreview => {
+                self.import.as_mut().filter(|preview| preview.editing_target).map(|preview| &mut preview.target)
+            }
+            _ => None,
+        }
+    }
+
+    /// Bracketed paste: teks langsung masuk ke input yang fokus
+    pub fn handle_paste(&mut self, text: &str) {
+        let Some(input) = self.active_text_input_mut() else { return };
+        input.insert_str(text);
+        if self.state == AppState::MusicPlayer
+            && matches!(self.mp_mode, MusicPlayerMode::AddSong | MusicPlayerMode::EditSong)
+            && !text.is_empty()
+        {
+            self.song_form_edited();
+        }
+    }
+    
+    /// `playlist.json` berubah di luar terminus saat ada perubahan yang belum tersimpan
+    fn handle_reload_conflict_mode(&mut self, key: KeyEvent) {
+        let Some(theirs) = self.pending_reload.clone() else {
+            self.mp_mode = MusicPlayerMode::Browse;
+            return;
+        };
+        match key.code {
+            KeyCode::Char('m') => {
+                let merged = merge_playlists(&self.disk_playlist, &self.playlist, &theirs);
+                self.pending_reload = None;
+                let before = self.snapshot(&[]);
+                self.disk_playlist = theirs;
+                self.replace_playlist(merged);
+                self.commit_edit("merge with playlist.json", before);
+                self.set_status("Merged changes from playlist.json");
+            }
+            KeyCode::Char('r') => {
+                self.pending_reload = None;
+                self.apply_reload(theirs);
+            }
+            KeyCode::Char('k') | KeyCode::Esc => {
+                // Simpan versi sendiri di atas file; versi disk masih bisa diambil dari backup
+                self.pending_reload = None;
+                self.disk_playlist = theirs;
+                self.persist_playlist();
+                if self.save_error.is_none() {
+                    self.set_status("Kept your playlist; playlist.json overwritten");
+                }
+            }
+            _ => return,
+        }
+        self.mp_mode = MusicPlayerMode::Browse;
+    }
+
+    fn handle_delete_confirm_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') => {
+                // Hapus lagu (atau semua lagu yang ditandai) dan kembali ke mode browse
+                if !self.playlist.is_empty() {
+                    let description = format!("delete of {}", self.describe_targets());
+                    let indices = self.target_indices();
+                    let before = self.snapshot(&[]);
+                    self.remove_songs(&indices);
+                    self.commit_edit(description, before); // Simpan playlist ke disk
+                }
+                self.mp_mode = MusicPlayerMode::Browse;
+            },
+            KeyCode::Char('n') | KeyCode::Esc => {
+                // Batal hapus
+                self.mp_mode = MusicPlayerMode::Browse;
+            },
+            _ => {}
+        }
+    }
+    
+    // Fungsi player kontrol
+     pub fn play_selected_song(&mut self) {
+        // Hentikan jika kosong
+        if self.playlist.is_empty() {
+            return;
+        }
+        let song = self.playlist[self.selected_song_index].clone();
+        self.play_song(song);
+    }
+
+    pub fn play_song(&mut self, song: Song) {
+        self.play_song_from(song, None);
+    }
+
+    /// Putar lagu, opsional mulai dari detik tertentu
+    pub fn play_song_from(&mut self, song: Song, start: Option<f64>) {
+        self.stop_playback();
+
+        // Validasi sumber sebelum mpv dijalankan
+        let source = match SongSource::of(&song) {
+            Ok(source) => source,
+            Err(e) => {
+                self.set_error(format!("Cannot play \"{}\": {}", song.title, e));
+                self.source_errors.insert(song_source(&song), e.to_string());
+                return;
+            }
+        };
+
+        // Salinan offline dipakai kalau ada; kalau belum, unduh sambil streaming
+        let source = match AudioCache::key(&song) {
+            Some(key) => match self.cache.cached_path(&key) {
+                Some(path) => {
+                    self.cache.touch(&key);
+                    self.save_cache();
+                    SongSource::LocalFile(path)
+                }
+                None => {
+                    if self.config.cache.auto {
+                        self.cache.request(&key);
+                    }
+                    source
+                }
+            },
+            None => source,
+        };
+
+        log::info!("Playing \"{}\" from {}", song.title, source.to_mpv_arg());
 
         // Ambil info lagu
-        let song = &self.playlist[self.selected_song_index];
+        self.track_serial += 1;
         self.current_song = Some(song.title.clone());
+        self.speed = song.speed.unwrap_or(1.0).clamp(MIN_SPEED, MAX_SPEED);
         self.music_playing = true;
+        if song.radio && !source.is_local() {
+            self.radio_watcher = Some(IcyWatcher::spawn());
+        }
+        self.playing_source_kind = Some(source.kind());
+        self.spawn_player(&song, &source, start);
+        if self.player_process.is_some()
+            && let Some(notifier) = &self.notifier
+        {
+            notifier.track_changed(&song, &song.title);
+        }
+        if self.player_process.is_some() {
+            self.start_listen(&song);
+        }
+        self.playing_song = Some(song);
+    }
 
-        // EXPAND TILDE kalau ada
-        let source = if let Some(path) = &song.path {
-            if let Some(stripped) = path.strip_prefix("~/") {
-                let mut pb = dirs::home_dir()
-                    .unwrap_or_else(|| PathBuf::from("/"));
-                pb.push(stripped);
-                pb.to_string_lossy().into_owned()
-            } else {
-                path.clone()
-            }
-        } else {
-            song.url.clone()
-        };
-
-        // Spawn mpv
-        match Command::new("mpv")
-             .args(&[
-		"--no-video",
-		&format!("--volume={}", self.volume),
-		"--input-ipc-server=/tmp/mpvsocket",
-		&source,
-	    ])
-	    .spawn()
+    fn spawn_player(&mut self, song: &Song, source: &SongSource, start: Option<f64>) {
+        let mut command = Command::new("mpv");
+        let mut ipc_server = OsString::from("--input-ipc-server=");
+        ipc_server.push(mpv_ipc::socket_path());
+        command.args(["--no-video", &format!("--volume={}", self.volume)]).arg(ipc_server);
+        if let Some(start) = start {
+            command.arg(format!("--start={:.1}", start));
+        }
+        if self.speed != 1.0 {
+            command.args([&format!("--speed={}", self.speed), "--audio-pitch-correction=yes"]);
+        }
+        if let Some(device) = &self.config.audio_device {
+            command.arg(format!("--audio-device={}", device));
+        }
+
+        // Spawn mpv; `--` memastikan sumber tidak pernah dibaca sebagai opsi.
+        // Output di-pipe supaya tidak menimpa TUI; stdin ditutup supaya mpv
+        // tidak ikut membaca tombol.
+        match command
+            .args(["--", &source.to_mpv_arg()])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
         {
-            Ok(child) => {
+            Ok(mut child) => {
+                log::debug!("mpv started (pid {})", child.id());
+                let key = song_source(song);
+                self.diagnostics.entry(key.clone()).or_default().start_run(&source.to_mpv_arg());
+                self.mpv_output = Some((key, mpv_output::capture(&mut child)));
                 self.player_process = Some(child);
+                self.music_playing = true;
             }
             Err(e) => {
-                // Cetak error agar kelihatan di console
-                eprintln!(
-                    "⚠️ Gagal spawn mpv untuk `{}`: {}",
-                    source, e
-                );
+                // Jangan cetak ke stderr: terminal sedang di alternate screen
+                self.set_error(format!("Cannot start mpv for `{}`: {}", source.to_mpv_arg(), e));
                 self.music_playing = false;
                 self.current_song = None;
+                self.playing_song = None;
+                self.playing_source_kind = None;
+                self.radio_watcher = None;
             }
         };
     }
 
-    
+    /// Dipanggil tiap tick dari event loop: cek metadata radio dan apakah mpv sudah berhenti sendiri
+    pub fn on_tick(&mut self) {
+        if self.state == AppState::Logs {
+            self.log_view.refresh();
+        }
+        if self.speed_save_due.is_some_and(|due| Instant::now() >= due) {
+            self.flush_speed_save();
+        }
+        self.poll_playlist_file();
+        self.poll_device_scan();
+        self.poll_metadata();
+        self.update_album_art();
+        self.poll_cache();
+        self.poll_downloads();
+        self.poll_mpv_output(false);
+        self.poll_radio();
+        self.poll_podcasts();
+        self.poll_mpris();
+        self.poll_notifications();
+        self.poll_scrobbler();
+        self.track_listen();
+
+        if self.playing_episode.is_some()
+            && self.music_playing
+            && self.last_position_poll.elapsed() >= POSITION_POLL_INTERVAL
+        {
+            self.last_position_poll = Instant::now();
+            self.save_episode_position();
+        }
+
+        if let Some(at) = self.radio_reconnect_at {
+            if Instant::now() >= at {
+                self.radio_reconnect_at = None;
+                self.reconnect_radio();
+            }
+            return;
+        }
+
+        let status = match self.player_process.as_mut().map(|proc| proc.try_wait()) {
+            Some(Ok(Some(status))) => status,
+            _ => return,
+        };
+        self.player_process = None;
+        self.music_playing = false;
+        log::debug!("mpv exited: {}", status);
+        self.poll_mpv_output(true);
+
+        if let Some(song) = self.playing_song.clone() {
+            let key = song_source(&song);
+            if song.radio {
+                // Stream radio tidak punya akhir: sambungkan lagi dengan backoff
+                let delay = Duration::from_secs(1 << self.radio_attempts.min(5));
+                self.radio_attempts += 1;
+                self.radio_reconnect_at = Some(Instant::now() + delay);
+                self.set_warning(format!("Stream dropped, reconnecting in {}s", delay.as_secs()));
+            } else if status.success() {
+                self.source_errors.remove(&key);
+                self.finish_episode();
+            } else if let Some(error) = self.diagnostics.get(&key).and_then(|log| log.errors.last()) {
+                // Sudah dilaporkan saat output mpv dibaca
+                self.source_errors.insert(key, error.to_string());
+            } else {
+                // mpv keluar dengan error: sumber tidak bisa dibuka atau tidak terjangkau
+                let message = match status.code() {
+                    Some(code) => format!("unreachable or unplayable (mpv exit code {}, see [i] for details)", code),
+                    None => String::from("playback was terminated"),
+                };
+                self.set_error(format!("\"{}\": {}", song.title, message));
+                self.source_errors.insert(key, message);
+            }
+        }
+    }
+
+    /// Pindahkan output mpv ke log diagnostik lagu. `until_eof` dipakai
+    /// setelah mpv keluar supaya baris terakhir (biasanya error-nya) ikut terbaca.
+    fn poll_mpv_output(&mut self, until_eof: bool) {
+        let Some((key, rx)) = &self.mpv_output else { return };
+        let key = key.clone();
+        let mut lines: Vec<OutputLine> = rx.try_iter().collect();
+        if until_eof {
+            while let Ok(line) = rx.recv_timeout(Duration::from_millis(200)) {
+                lines.push(line);
+            }
+            self.mpv_output = None;
+        }
+        if lines.is_empty() {
+            return;
+        }
+
+        let log = self.diagnostics.entry(key.clone()).or_default();
+        let mut errors = Vec::new();
+        for line in lines {
+            log::trace!("mpv: {}", line.text);
+            errors.extend(log.push(line));
+        }
+        let title = self.playing_song.as_ref().map(|song| song.title.clone()).unwrap_or_default();
+        for error in errors {
+            self.set_error(format!("\"{}\": {}", title, error));
+            self.source_errors.insert(key.clone(), error.to_string());
+        }
+    }
+
+    /// Panel output mpv untuk lagu yang sedang diputar, atau lagu terpilih
+    fn open_diagnostics(&mut self) {
+        let song = match self.mp_mode {
+            MusicPlayerMode::NowPlaying => self.playing_song.clone(),
+            _ => None,
+        }
+        .or_else(|| self.playlist.get(self.selected_song_index).cloned());
+        let Some(song) = song else { return };
+        self.diagnostics_song = Some(song);
+        self.diagnostics_scroll = 0;
+        self.diagnostics_return = self.mp_mode;
+        self.mp_mode = MusicPlayerMode::Diagnostics;
+    }
+
+    fn handle_diagnostics_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('b') | KeyCode::Char('i') | KeyCode::Esc => self.mp_mode = self.diagnostics_return,
+            KeyCode::Up | KeyCode::PageUp => {
+                let lines = self
+                    .diagnostics_song
+                    .as_ref()
+                    .and_then(|song| self.diagnostics.get(&song_source(song)))
+                    .map_or(0, |log| log.lines.len());
+                let step = if key.code == KeyCode::Up { 1 } else { 20 };
+                self.diagnostics_scroll = (self.diagnostics_scroll + step).min(lines);
+            }
+            KeyCode::Down => self.diagnostics_scroll = self.diagnostics_scroll.saturating_sub(1),
+            KeyCode::PageDown => self.diagnostics_scroll = self.diagnostics_scroll.saturating_sub(20),
+            KeyCode::End => self.diagnostics_scroll = 0,
+            KeyCode::Char('c') => {
+                if let Some(song) = &self.diagnostics_song {
+                    self.diagnostics.remove(&song_source(song));
+                }
+                self.diagnostics_scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn reconnect_radio(&mut self) {
+        let Some(song) = self.playing_song.clone() else { return };
+        match SongSource::of(&song) {
+            Ok(source) => {
+                self.spawn_player(&song, &source, None);
+                // `spawn_player` mengosongkan state kalau gagal; pertahankan supaya dicoba lagi
+                if self.player_process.is_none() {
+                    self.playing_song = Some(song);
+                    self.playing_source_kind = Some(source.kind());
+                    self.radio_reconnect_at = Some(Instant::now() + Duration::from_secs(5));
+                }
+            }
+            Err(e) => {
+                self.set_error(format!("Cannot reconnect \"{}\": {}", song.title, e));
+                self.source_errors.insert(song_source(&song), e.to_string());
+            }
+        }
+    }
+
+    /// Ambil event dari pembaca metadata ICY
+    fn poll_radio(&mut self) {
+        let events: Vec<RadioEvent> = match &self.radio_watcher {
+            Some(watcher) => watcher.events.try_iter().collect(),
+            None => return,
+        };
+        let Some(station) = self.playing_song.as_ref().map(song_source) else { return };
+
+        for event in events {
+            match event {
+                RadioEvent::Connected { station: name } => {
+                    self.radio_attempts = 0;
+                    if name.is_some() {
+                        self.station_name = name;
+                    }
+                }
+                RadioEvent::Title(title) => {
+                    if let (Some(notifier), Some(song)) = (&self.notifier, &self.playing_song) {
+                        notifier.track_changed(song, &title);
+                    }
+                    self.current_song = Some(title.clone());
+                    self.radio_history.record(&station, &title);
+                    if let Err(e) = self.radio_history.save() {
+                        self.set_warning(format!("Cannot save radio history: {}", e));
+                    }
+                    self.stream_title = Some(title);
+                }
+            }
+        }
+    }
+
+    /// Error terakhir untuk sumber lagu ini, kalau ada
+    pub fn source_error(&self, song: &Song) -> Option<&str> {
+        self.source_errors.get(&song_source(song)).map(String::as_str)
+    }
+
      pub fn toggle_playback(&mut self) {
-        self.music_playing = !self.music_playing;
+        self.set_paused(self.music_playing);
+    }
+
+    /// Pause / lanjutkan mpv lewat IPC
+    pub fn set_paused(&mut self, paused: bool) {
+        if self.player_process.is_none() {
+            return;
+        }
+        match mpv_ipc::set_property("pause", json!(paused)) {
+            Ok(_) => self.music_playing = !paused,
+            Err(e) => self.set_error(format!("Cannot reach mpv: {}", e)),
+        }
+    }
+
+    /// Ubah volume (0-100) dan terapkan langsung ke mpv yang sedang jalan
+    pub fn set_volume(&mut self, volume: u8) {
+        self.volume = volume.min(100);
+        if self.player_process.is_some()
+            && let Err(e) = mpv_ipc::set_property("volume", json!(self.volume))
+        {
+            self.set_error(format!("Cannot change volume: {}", e));
+        }
+    }
+
+    /// Ubah kecepatan putar (0.5x–2.0x) dan ingat untuk lagu ini
+    pub fn set_speed(&mut self, speed: f64) {
+        let Some(song) = &self.playing_song else { return };
+        if song.radio {
+            self.set_warning("Speed cannot be changed for radio streams");
+            return;
+        }
+        // Bulatkan ke 0.1 supaya langkah berulang tidak menumpuk error float
+        let speed = ((speed * 10.0).round() / 10.0).clamp(MIN_SPEED, MAX_SPEED);
         if self.player_process.is_some() {
-            // Ini cuma dummy echo; bisa diganti dengan true IPC command
-            let _ = Command::new("echo")
-                .args(&["cycle", "pause"])
-                .output();
+            // Pitch correction default-nya sudah aktif; set lagi untuk jaga-jaga
+            let result = mpv_ipc::set_property("audio-pitch-correction", json!(true))
+                .and_then(|_| mpv_ipc::set_property("speed", json!(speed)));
+            if let Err(e) = result {
+                self.set_error(format!("Cannot change speed: {}", e));
+                return;
+            }
+        }
+        self.speed = speed;
+
+        let saved = (speed != 1.0).then_some(speed);
+        let Some(song) = self.playing_song.as_mut() else { return };
+        song.speed = saved;
+        let key = dedupe::song_key(song);
+        for entry in self.playlist.iter_mut().filter(|entry| dedupe::song_key(entry) == key) {
+            if entry.speed != saved {
+                entry.speed = saved;
+                self.speed_save_due = Some(Instant::now() + SPEED_SAVE_DELAY);
+            }
+        }
+    }
+
+    /// Simpan perubahan kecepatan yang masih ditunda
+    pub fn flush_speed_save(&mut self) {
+        if self.speed_save_due.take().is_some() {
+            self.persist_playlist();
+        }
+    }
+
+    /// Seperti tombol `l` di mpv: set titik A, lalu B, lalu hapus loop
+    pub fn cycle_ab_loop(&mut self) {
+        let Some(song) = &self.playing_song else { return };
+        if song.radio {
+            self.set_warning("A-B loop is not available for radio streams");
+            return;
+        }
+        if self.player_process.is_none() {
+            return;
+        }
+        if self.loop_a.is_some() && self.loop_b.is_some() {
+            self.clear_ab_loop();
+            return;
+        }
+
+        let position = match mpv_ipc::get_property("time-pos") {
+            Ok(position) => position.as_f64(),
+            Err(e) => {
+                self.set_error(format!("Cannot read playback position: {}", e));
+                return;
+            }
+        };
+        let Some(position) = position else { return };
+        match self.loop_a {
+            None => {
+                if let Err(e) = mpv_ipc::set_property("ab-loop-a", json!(position)) {
+                    self.set_error(format!("Cannot set loop start: {}", e));
+                    return;
+                }
+                self.loop_a = Some(position);
+                self.set_status(format!(
+                    "Loop start at {}; press [l] again at the end point",
+                    podcast::format_duration(position as u64)
+                ));
+            }
+            Some(a) if position <= a => self.set_warning("Loop end must be after the loop start"),
+            Some(a) => {
+                if let Err(e) = mpv_ipc::set_property("ab-loop-b", json!(position)) {
+                    self.set_error(format!("Cannot set loop end: {}", e));
+                    return;
+                }
+                self.loop_b = Some(position);
+                self.set_status(format!(
+                    "Looping {} – {}",
+                    podcast::format_duration(a as u64),
+                    podcast::format_duration(position as u64)
+                ));
+            }
+        }
+    }
+
+    fn clear_ab_loop(&mut self) {
+        self.loop_a = None;
+        self.loop_b = None;
+        let result = mpv_ipc::set_property("ab-loop-a", json!("no"))
+            .and_then(|_| mpv_ipc::set_property("ab-loop-b", json!("no")));
+        match result {
+            Ok(_) => self.set_status("A-B loop cleared"),
+            Err(e) => self.set_error(format!("Cannot clear loop: {}", e)),
+        }
+    }
+
+    /// Seek relatif (`absolute = false`) atau ke posisi tertentu, dalam detik
+    pub fn seek(&mut self, seconds: f64, absolute: bool) {
+        if self.player_process.is_none() {
+            return;
+        }
+        let mode = if absolute { "absolute" } else { "relative" };
+        if let Err(e) = mpv_ipc::command(json!(["seek", seconds, mode])) {
+            self.set_error(format!("Seek failed: {}", e));
+        }
+    }
+
+    /// Nyalakan/matikan desktop notification dan simpan ke config
+    pub fn toggle_notifications(&mut self) {
+        self.config.notifications = !self.config.notifications;
+        self.notifier = None;
+        if self.config.notifications {
+            match Notifier::start() {
+                Ok(notifier) => self.notifier = Some(notifier),
+                Err(e) => self.set_warning(format!("Notifications unavailable: {}", e)),
+            }
+        }
+        if let Err(e) = self.config.save() {
+            self.set_error(format!("Cannot save config: {}", e));
+        } else if self.notifier.is_some() || !self.config.notifications {
+            let state = if self.config.notifications { "on" } else { "off" };
+            self.set_status(format!("Notifications {}", state));
+        }
+    }
+
+    /// Mulai menghitung listen dan kirim "now playing". Radio dan lagu tanpa
+    /// artist dilewati karena server scrobble butuh artist + judul.
+    fn start_listen(&mut self, song: &Song) {
+        self.listen_tracker = None;
+        let Some(scrobbler) = &self.scrobbler else { return };
+        if song.radio || song.artist.trim().is_empty() || song.title.trim().is_empty() {
+            return;
+        }
+        let listen = Listen::new(song.title.trim(), song.artist.trim(), &song.url);
+        scrobbler.now_playing(listen.clone());
+        self.listen_tracker = Some(ListenTracker::new(listen));
+    }
+
+    /// Tambah waktu dengar selama musik tidak di-pause dan submit saat syarat terpenuhi
+    fn track_listen(&mut self) {
+        let elapsed = self.last_tick.elapsed();
+        self.last_tick = Instant::now();
+        if !self.music_playing {
+            return;
+        }
+        let Some(tracker) = &mut self.listen_tracker else { return };
+
+        if tracker.needs_duration() {
+            tracker.listen.duration = mpv_ipc::get_property("duration")
+                .ok()
+                .and_then(|value| value.as_f64())
+                .map(|secs| secs.round() as u64);
+        }
+        if tracker.advance(elapsed)
+            && let Some(scrobbler) = &self.scrobbler
+        {
+            scrobbler.submit(tracker.listen.clone());
+        }
+    }
+
+    fn poll_scrobbler(&mut self) {
+        let Some(scrobbler) = &self.scrobbler else { return };
+        let events: Vec<ScrobbleEvent> = scrobbler.events.try_iter().collect();
+        for event in events {
+            match event {
+                ScrobbleEvent::Sent { count } => self.set_status(format!("Scrobbled {} listen(s)", count)),
+                ScrobbleEvent::Rejected { title, error } => {
+                    self.set_warning(format!("Scrobble for \"{}\" rejected: {}", title, error));
+                }
+                ScrobbleEvent::Failed { error, retry_in, queued } => self.set_warning(format!(
+                    "Scrobble failed ({}), {} queued, retrying in {}s",
+                    error,
+                    queued,
+                    retry_in.as_secs()
+                )),
+            }
+        }
+    }
+
+    /// Jalankan perintah dari `terminus ctl`; dipanggil dari event loop
+    pub fn apply_control(&mut self, command: ControlCommand) -> Result<serde_json::Value, String> {
+        log::debug!("Control command: {:?}", command);
+        match command {
+            ControlCommand::Play(None) if self.player_process.is_some() => self.set_paused(false),
+            ControlCommand::Play(None) => self.play_selected_song(),
+            ControlCommand::Play(Some(query)) => match cli::find_song(&self.playlist, &query) {
+                Some(index) => {
+                    self.selected_song_index = index;
+                    self.play_selected_song();
+                }
+                None => self.play_song(cli::resolve_song(&self.playlist, &query)?),
+            },
+            ControlCommand::Pause | ControlCommand::Toggle => {
+                let paused = command == ControlCommand::Pause || self.music_playing;
+                self.set_paused(paused);
+                // `set_paused` tidak mengubah state kalau IPC ke mpv gagal
+                if self.player_process.is_some() && self.music_playing == paused {
+                    return Err(self.current_status().unwrap_or("cannot reach mpv").to_string());
+                }
+            }
+            ControlCommand::Next => self.next_song(),
+            ControlCommand::Prev => self.prev_song(),
+            ControlCommand::Volume(Some(VolumeChange::Set(volume))) => self.set_volume(volume),
+            ControlCommand::Volume(Some(VolumeChange::Delta(delta))) => {
+                self.set_volume((i16::from(self.volume) + delta).clamp(0, 100) as u8);
+            }
+            ControlCommand::Volume(None) => return Ok(json!({ "volume": self.volume })),
+            ControlCommand::Enqueue(query) => {
+                let song = cli::resolve_song(&self.playlist, &query)?;
+                let title = song.title.clone();
+                self.queue.push_back(song);
+                return Ok(json!({ "queued": title, "queue": self.queue.len() }));
+            }
+            ControlCommand::Status => return Ok(self.control_status()),
+        }
+
+        // Perintah playback gagal kalau mpv tidak jalan (pesan ada di status)
+        if self.player_process.is_none() {
+            return Err(self.current_status().unwrap_or("nothing is playing").to_string());
+        }
+        Ok(self.control_status())
+    }
+
+    fn control_status(&self) -> serde_json::Value {
+        let state = match (&self.playing_song, self.music_playing) {
+            (None, _) => "stopped",
+            (Some(_), true) => "playing",
+            (Some(_), false) => "paused",
+        };
+        json!({
+            "state": state,
+            "title": self.playing_song.as_ref().map(|song| song.title.clone()),
+            "artist": self.playing_song.as_ref().map(|song| song.artist.clone()),
+            "source": self.playing_song.as_ref().map(song_source),
+            "stream_title": self.stream_title,
+            "volume": self.volume,
+            "queue": self.queue.len(),
+        })
+    }
+
+    /// Tombol Next/Pause yang ditekan di desktop notification
+    fn poll_notifications(&mut self) {
+        let Some(notifier) = &self.notifier else { return };
+        let actions: Vec<NotifyAction> = notifier.actions.try_iter().collect();
+        for action in actions {
+            match action {
+                NotifyAction::Next => self.next_song(),
+                NotifyAction::Pause => self.set_paused(true),
+            }
+        }
+    }
+
+    /// Jalankan perintah dari client MPRIS dan kirim keadaan terbaru
+    fn poll_mpris(&mut self) {
+        let Some(server) = &self.mpris else { return };
+        let commands: Vec<MprisCommand> = server.commands.try_iter().collect();
+
+        for command in commands {
+            let seeked = matches!(command, MprisCommand::Seek(_) | MprisCommand::SetPosition(_));
+            match command {
+                MprisCommand::PlayPause if self.player_process.is_some() => self.toggle_playback(),
+                MprisCommand::Play if self.player_process.is_some() => self.set_paused(false),
+                MprisCommand::PlayPause | MprisCommand::Play => self.play_selected_song(),
+                MprisCommand::Pause => self.set_paused(true),
+                MprisCommand::Stop => self.stop_playback(),
+                MprisCommand::Next => self.next_song(),
+                MprisCommand::Previous => self.prev_song(),
+                MprisCommand::Seek(offset) => self.seek(offset as f64 / 1_000_000.0, false),
+                MprisCommand::SetPosition(position) => self.seek(position as f64 / 1_000_000.0, true),
+                MprisCommand::SetVolume(volume) => self.set_volume((volume * 100.0).round() as u8),
+                MprisCommand::SetRate(rate) => self.set_speed(rate),
+                MprisCommand::OpenUri(uri) => self.play_song(Song {
+                    title: uri.clone(),
+                    artist: String::new(),
+                    url: uri,
+                    path: None,
+                    radio: false,
+                    speed: None,
+                    duration: None,
+                }),
+            }
+            if seeked && let Some(server) = &self.mpris {
+                server.seeked();
+            }
+        }
+
+        let state = match &self.playing_song {
+            Some(song) => MprisState {
+                status: if self.music_playing { PlaybackStatus::Playing } else { PlaybackStatus::Paused },
+                // Untuk radio, judul yang sedang on air lebih berguna
+                title: self.stream_title.clone().unwrap_or_else(|| song.title.clone()),
+                artist: song.artist.clone(),
+                url: song_source(song),
+                length: song.duration.map(|secs| secs as i64 * 1_000_000),
+                volume: f64::from(self.volume) / 100.0,
+                rate: self.speed,
+                track: self.track_serial,
+            },
+            None => MprisState {
+                volume: f64::from(self.volume) / 100.0,
+                rate: 1.0,
+                ..Default::default()
+            },
+        };
+        if let Some(server) = &mut self.mpris {
+            server.update(state);
         }
     }
     
     /// Stop dan kill proses mpv
     pub fn stop_playback(&mut self) {
+        if self.player_process.is_some() {
+            self.save_episode_position();
+        }
+        self.playing_episode = None;
+        self.listen_tracker = None;
         self.music_playing = false;
         self.current_song = None;
+        self.playing_song = None;
+        self.playing_source_kind = None;
+        self.radio_watcher = None;
+        self.station_name = None;
+        self.stream_title = None;
+        self.radio_reconnect_at = None;
+        self.radio_attempts = 0;
+        self.mpv_output = None;
+        // Loop milik proses mpv lama; kecepatan diambil lagi dari lagu berikutnya
+        self.loop_a = None;
+        self.loop_b = None;
+        self.speed = 1.0;
+        self.flush_speed_save();
         if let Some(mut proc) = self.player_process.take() {
-            let _ = proc.kill();
-            let _ = proc.wait();
+            if let Err(e) = proc.kill().and_then(|_| proc.wait()) {
+                self.set_warning(format!("Cannot stop mpv: {}", e));
+            }
+            // mpv yang di-kill tidak sempat menghapus socket-nya sendiri
+            let _ = fs::remove_file(mpv_ipc::socket_path());
         }
     }
 
     /// Next / prev
     pub fn next_song(&mut self) {
+        // Lagu di queue diputar lebih dulu
+        if let Some(song) = self.queue.pop_front() {
+            self.play_song(song);
+            return;
+        }
         if !self.playlist.is_empty() {
             self.selected_song_index =
                 (self.selected_song_index + 1) % self.playlist.len();
@@ -514,7 +2987,10 @@ impl App {
             MenuItem::MusicPlayer => String::from("🎵 Music Player"),
             MenuItem::VideoPlayer => String::from("🎥 Video Player"),
             MenuItem::FileTools => String::from("📁 File Tools"),
+            MenuItem::Downloads => String::from("⬇ Downloads"),
             MenuItem::ComingSoon => String::from("🧪 Coming Soon"),
+            MenuItem::Settings => String::from("⚙ Settings"),
+            MenuItem::Logs => String::from("📜 Logs"),
             MenuItem::Quit => String::from("❌ Quit"),
         }
     }
@@ -524,8 +3000,249 @@ impl App {
             MenuItem::MusicPlayer => String::from("Listen to curated or local playlists"),
             MenuItem::VideoPlayer => String::from("Watch videos in terminal (mpv-based)"),
             MenuItem::FileTools => String::from("Future toolkit: rename, organize, compress"),
-            MenuItem::ComingSoon => String::from("Markdown viewer, API tester, YouTube DL?"),
+            MenuItem::Downloads => String::from("Download audio or video with yt-dlp"),
+            MenuItem::ComingSoon => String::from("Markdown viewer, API tester, chat client"),
+            MenuItem::Settings => String::from("Choose the audio output device"),
+            MenuItem::Logs => String::from("Browse the application log for diagnosing playback problems"),
             MenuItem::Quit => String::from("Exit the application"),
         }
     }
 }
+
+/// Path lokal (dengan tilde di-expand) atau URL yang diberikan ke player
+pub fn song_source(song: &Song) -> String {
+    match &song.path {
+        Some(path) => expand_tilde(path).to_string_lossy().into_owned(),
+        None => song.url.clone(),
+    }
+}
+
+pub fn expand_tilde(path: &str) -> PathBuf {
+    if let Some(stripped) = path.strip_prefix("~/") {
+        let mut pb = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        pb.push(stripped);
+        pb
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Merge tiga arah berdasarkan `song_key`: `base` adalah isi disk terakhir
+/// yang diketahui. Lagu yang dihapus di disk ikut dihapus, lagu baru di disk
+/// ditambahkan di akhir, dan edit metadata di disk dipakai kalau lagu itu
+/// tidak diedit di dalam aplikasi.
+fn merge_playlists(base: &[Song], mine: &[Song], theirs: &[Song]) -> Vec<Song> {
+    let base: HashMap<String, &Song> = base.iter().map(|song| (dedupe::song_key(song), song)).collect();
+    let theirs_by_key: HashMap<String, &Song> = theirs.iter().map(|song| (dedupe::song_key(song), song)).collect();
+
+    let mut merged: Vec<Song> = Vec::new();
+    let mut seen = HashSet::new();
+    for song in mine {
+        let key = dedupe::song_key(song);
+        match (base.get(&key), theirs_by_key.get(&key)) {
+            // Dihapus di disk dan tidak diubah di sini
+            (Some(old), None) if *old == song => continue,
+            (Some(old), Some(new)) if *old == song => merged.push((*new).clone()),
+            _ => merged.push(song.clone()),
+        }
+        seen.insert(key);
+    }
+    for song in theirs {
+        let key = dedupe::song_key(song);
+        if !base.contains_key(&key) && seen.insert(key) {
+            merged.push(song.clone());
+        }
+    }
+    merged
+}
+
+/// Tambahkan lagu yang belum ada di `playlist`; kembalikan jumlah yang masuk
+fn append_new(playlist: &mut Vec<Song>, songs: Vec<Song>) -> usize {
+    let before = playlist.len();
+    for song in songs {
+        if dedupe::find_duplicate_of(playlist, &song, None).is_none() {
+            playlist.push(song);
+        }
+    }
+    playlist.len() - before
+}
+
+/// Baca playlist M3U/M3U8. Judul dan artist diambil dari `#EXTINF` kalau
+/// formatnya `artist - judul`; path relatif dihitung dari folder file M3U.
+pub fn read_m3u(path: &Path) -> io::Result<Vec<Song>> {
+    let content = fs::read_to_string(path)?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    let mut songs = Vec::new();
+    let mut info: Option<(String, String)> = None;
+    let mut duration: Option<u64> = None;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            let (length, label) = extinf.split_once(',').unwrap_or((extinf, ""));
+            let label = label.trim();
+            // `-1` berarti durasi tidak diketahui
+            duration = length.trim().parse::<f64>().ok().filter(|secs| *secs > 0.0).map(|secs| secs.round() as u64);
+            info = Some(match label.split_once(" - ") {
+                Some((artist, title)) => (title.trim().to_string(), artist.trim().to_string()),
+                None => (label.to_string(), String::new()),
+            });
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let is_url = url::Url::parse(line).is_ok_and(|url| url.scheme().len() > 1);
+        let (url, song_path) = if is_url {
+            (line.to_string(), None)
+        } else {
+            let local = expand_tilde(line);
+            let local = if local.is_absolute() { local } else { base.join(local) };
+            (String::new(), Some(local.to_string_lossy().into_owned()))
+        };
+        let (title, artist) = info.take().unwrap_or_else(|| {
+            let name = Path::new(line)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| line.to_string());
+            (name, String::new())
+        });
+        let radio = is_url && matches!(SongSource::parse(line), Ok(SongSource::Stream(_)));
+        songs.push(Song { title, artist, url, path: song_path, radio, speed: None, duration: duration.take() });
+    }
+    Ok(songs)
+}
+
+/// Tulis lagu sebagai extended M3U
+pub fn write_m3u(path: &Path, songs: &[Song]) -> io::Result<()> {
+    let mut content = String::from("#EXTM3U\n");
+    for song in songs {
+        let length = song.duration.map(|secs| secs as i64).unwrap_or(-1);
+        if song.artist.is_empty() {
+            content.push_str(&format!("#EXTINF:{},{}\n", length, song.title));
+        } else {
+            content.push_str(&format!("#EXTINF:{},{} - {}\n", length, song.artist, song.title));
+        }
+        content.push_str(&song_source(song));
+        content.push('\n');
+    }
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(title: &str, artist: &str, url: &str, path: Option<&str>, duration: Option<u64>) -> Song {
+        Song {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            url: url.to_string(),
+            path: path.map(str::to_string),
+            radio: false,
+            speed: None,
+            duration,
+        }
+    }
+
+    #[test]
+    fn m3u_round_trips_durations() {
+        let dir = std::env::temp_dir().join(format!("terminus-m3u-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("list.m3u");
+        let songs = vec![
+            song("Song", "Artist", "https://www.youtube.com/watch?v=dQw4w9WgXcQ", None, Some(213)),
+            song("Local", "", "", Some("/music/local.mp3"), None),
+        ];
+
+        write_m3u(&file, &songs).unwrap();
+        let content = fs::read_to_string(&file).unwrap();
+        let read = read_m3u(&file);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(content.contains("#EXTINF:213,Artist - Song\n"));
+        assert!(content.contains("#EXTINF:-1,Local\n"));
+        assert_eq!(read.unwrap(), songs);
+    }
+
+    #[test]
+    fn m3u_durations_are_rounded_and_scoped_to_one_entry() {
+        let dir = std::env::temp_dir().join(format!("terminus-m3u-read-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("list.m3u");
+        fs::write(&file, "#EXTM3U\n#EXTINF:95.6,A - One\none.mp3\ntwo.mp3\n#EXTINF:0,Three\nthree.mp3\n").unwrap();
+        let read = read_m3u(&file);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let durations: Vec<_> = read.unwrap().iter().map(|song| song.duration).collect();
+        assert_eq!(durations, [Some(96), None, None]);
+    }
+
+    fn titles(songs: &[Song]) -> Vec<&str> {
+        songs.iter().map(|song| song.title.as_str()).collect()
+    }
+
+    fn base_playlist() -> Vec<Song> {
+        ["a", "b", "c"]
+            .iter()
+            .map(|name| song(name, "", &format!("https://example.com/{}", name), None, None))
+            .collect()
+    }
+
+    #[test]
+    fn merge_keeps_local_edit_of_song_deleted_on_disk() {
+        let base = base_playlist();
+        let mut mine = base.clone();
+        mine[1].artist = String::from("Edited here");
+        let theirs = vec![base[0].clone(), base[2].clone()];
+
+        let merged = merge_playlists(&base, &mine, &theirs);
+        assert_eq!(titles(&merged), ["a", "b", "c"]);
+        assert_eq!(merged[1].artist, "Edited here");
+
+        // Tanpa edit lokal, hapus di disk ikut diterapkan
+        let merged = merge_playlists(&base, &base, &theirs);
+        assert_eq!(titles(&merged), ["a", "c"]);
+    }
+
+    #[test]
+    fn merge_prefers_local_edit_when_both_sides_edit_a_song() {
+        let base = base_playlist();
+        let mut mine = base.clone();
+        let mut theirs = base.clone();
+        mine[0].title = String::from("mine");
+        theirs[0].title = String::from("theirs");
+        theirs[2].artist = String::from("Disk only");
+
+        let merged = merge_playlists(&base, &mine, &theirs);
+        assert_eq!(titles(&merged), ["mine", "b", "c"]);
+        assert_eq!(merged[2].artist, "Disk only");
+    }
+
+    #[test]
+    fn merge_keeps_songs_added_on_both_sides() {
+        let base = base_playlist();
+        let mut mine = base.clone();
+        let mut theirs = base.clone();
+        mine.push(song("local", "", "https://example.com/local", None, None));
+        theirs.insert(0, song("disk", "", "https://example.com/disk", None, None));
+        // Lagu yang sama ditambahkan di kedua sisi hanya muncul sekali
+        mine.push(song("both", "", "https://youtu.be/dQw4w9WgXcQ", None, None));
+        theirs.push(song("both", "", "https://www.youtube.com/watch?v=dQw4w9WgXcQ", None, None));
+
+        let merged = merge_playlists(&base, &mine, &theirs);
+        assert_eq!(titles(&merged), ["a", "b", "c", "local", "both", "disk"]);
+    }
+
+    #[test]
+    fn merge_of_reordered_list_keeps_every_song_once() {
+        let base = base_playlist();
+        let theirs = vec![base[2].clone(), base[0].clone(), base[1].clone()];
+        let merged = merge_playlists(&base, &base, &theirs);
+        assert_eq!(titles(&merged), ["a", "b", "c"]);
+
+        let mine = vec![base[1].clone(), base[2].clone(), base[0].clone()];
+        let merged = merge_playlists(&base, &mine, &base);
+        assert_eq!(titles(&merged), ["b", "c", "a"]);
+    }
+}
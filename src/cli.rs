@@ -0,0 +1,349 @@
+// src/cli.rs
+use std::{
+    path::{Path, PathBuf},
+    process::Command as Process,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use serde_json::json;
+
+use crate::app::{read_m3u, song_source, write_m3u, App, AppState, Song};
+use crate::schema::{self, PlaylistLoad};
+use crate::storage::{self, InstanceLock};
+use crate::tools::control;
+use crate::tools::dedupe;
+use crate::tools::source::SongSource;
+
+/// Seperti `println!`, tapi tidak panic kalau stdout ditutup (mis. `| head`)
+macro_rules! out {
+    ($($arg:tt)*) => {{
+        use std::io::Write;
+        let _ = writeln!(std::io::stdout(), $($arg)*);
+    }};
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "terminus", version, about = "Terminal multimedia toolkit")]
+pub struct Cli {
+    /// Langsung buka tool tertentu, tanpa lewat main menu
+    #[arg(long, value_enum)]
+    pub screen: Option<Screen>,
+
+    /// Tinjau dan import playlist dari output `yt-dlp --flat-playlist -J`;
+    /// `-` membaca dari stdin
+    #[arg(long, value_name = "FILE")]
+    pub import: Option<PathBuf>,
+
+    /// Output JSON untuk script
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Level log file: off, error, warn, info, debug, trace
+    /// (default: env TERMINUS_LOG, lalu info)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    pub log_level: Option<LevelFilter>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Screen {
+    Music,
+    Video,
+    Files,
+}
+
+impl Screen {
+    pub fn state(self) -> AppState {
+        match self {
+            Screen::Music => AppState::MusicPlayer,
+            Screen::Video => AppState::VideoPlayer,
+            Screen::Files => AppState::FileTools,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Kelola playlist utama
+    Playlist {
+        #[command(subcommand)]
+        action: PlaylistCommand,
+    },
+    /// Putar lagu dari playlist (nomor atau judul) atau path/URL langsung
+    Play {
+        song: String,
+    },
+    /// Kirim perintah ke terminus yang sedang jalan:
+    /// play [song], pause, toggle, next, prev, volume [N|+N|-N], enqueue <song>, status
+    Ctl {
+        // `allow_hyphen_values` supaya `volume -5` tidak dibaca sebagai flag
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PlaylistCommand {
+    /// Tampilkan semua lagu
+    List,
+    /// Tambah lagu dari URL atau path lokal
+    Add {
+        source: String,
+        #[arg(long, short)]
+        title: Option<String>,
+        #[arg(long, short, default_value = "")]
+        artist: String,
+    },
+    /// Hapus lagu berdasarkan nomor (mulai dari 1) atau judul
+    Remove {
+        song: String,
+    },
+    /// Tambahkan lagu dari file .json atau .m3u/.m3u8; duplikat dilewati
+    Import {
+        file: PathBuf,
+    },
+    /// Simpan playlist ke file .json atau .m3u/.m3u8
+    Export {
+        file: PathBuf,
+    },
+}
+
+/// Jalankan subcommand tanpa TUI. Hasilnya exit code proses.
+pub fn run(command: Command, json: bool) -> i32 {
+    let result = match command {
+        Command::Playlist { action } => run_playlist(action, json),
+        Command::Play { song } => play(&song, json),
+        Command::Ctl { command } => ctl(&command.join(" "), json),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            if json {
+                out!("{}", json!({ "error": e.to_string() }));
+            } else {
+                eprintln!("terminus: {}", e);
+            }
+            1
+        }
+    }
+}
+
+type CliResult = Result<i32, Box<dyn std::error::Error>>;
+
+/// Playlist utama; gagal (tanpa menulis apa pun) kalau file-nya tidak terbaca
+fn load() -> Result<Vec<Song>, String> {
+    match App::load_playlist() {
+        PlaylistLoad::Missing => Ok(App::default_playlist()),
+        PlaylistLoad::Loaded(songs) => Ok(songs),
+        PlaylistLoad::Recovered { songs, quarantined, backup, error } => {
+            eprintln!(
+                "terminus: playlist.json was corrupt ({}); moved to {} and restored from {}",
+                error,
+                quarantined.display(),
+                backup.display()
+            );
+            Ok(songs)
+        }
+        PlaylistLoad::Quarantined { quarantined, error } => Err(format!(
+            "playlist.json was corrupt ({}) and has been moved to {}; no readable backup found",
+            error,
+            quarantined.display()
+        )),
+        PlaylistLoad::Unreadable(message) => Err(message),
+    }
+}
+
+fn song_json(index: usize, song: &Song) -> serde_json::Value {
+    json!({
+        "index": index + 1,
+        "title": song.title,
+        "artist": song.artist,
+        "url": song.url,
+        "path": song.path,
+        "radio": song.radio,
+        "speed": song.speed.unwrap_or(1.0),
+        "duration": song.duration,
+    })
+}
+
+fn run_playlist(action: PlaylistCommand, json: bool) -> CliResult {
+    // Jangan menulis saat TUI sedang berjalan; perubahannya akan tertimpa
+    let _lock = match action {
+        PlaylistCommand::List | PlaylistCommand::Export { .. } => None,
+        _ => {
+            let path = App::playlist_lock_path().ok_or("Config directory not found")?;
+            let lock = InstanceLock::acquire(&path)?
+                .ok_or("the playlist is open in a running terminus; use `terminus ctl` instead")?;
+            Some(lock)
+        }
+    };
+    let mut playlist = load()?;
+
+    match action {
+        PlaylistCommand::List => {
+            if json {
+                let songs: Vec<_> = playlist.iter().enumerate().map(|(i, song)| song_json(i, song)).collect();
+                out!("{}", serde_json::to_string_pretty(&songs)?);
+            } else {
+                for (i, song) in playlist.iter().enumerate() {
+                    let artist = if song.artist.is_empty() { String::new() } else { format!(" - {}", song.artist) };
+                    out!("{:>3}. {}{}  [{}]", i + 1, song.title, artist, song_source(song));
+                }
+            }
+        }
+        PlaylistCommand::Add { source, title, artist } => {
+            let title = title.unwrap_or_else(|| default_title(&source));
+            let song = Song::from_input(&title, &artist, &source)?;
+            if let Some(existing) = dedupe::find_duplicate_of(&playlist, &song, None) {
+                return Err(format!("already in playlist as #{} \"{}\"", existing + 1, playlist[existing].title).into());
+            }
+            playlist.push(song);
+            App::write_playlist(&playlist, true)?;
+            let index = playlist.len() - 1;
+            if json {
+                out!("{}", song_json(index, &playlist[index]));
+            } else {
+                out!("Added #{} {}", index + 1, playlist[index].title);
+            }
+        }
+        PlaylistCommand::Remove { song } => {
+            let index = find_song(&playlist, &song).ok_or_else(|| format!("no song matches \"{}\"", song))?;
+            let removed = playlist.remove(index);
+            App::write_playlist(&playlist, true)?;
+            if json {
+                out!("{}", song_json(index, &removed));
+            } else {
+                out!("Removed #{} {}", index + 1, removed.title);
+            }
+        }
+        PlaylistCommand::Import { file } => {
+            let songs = if is_m3u(&file) {
+                read_m3u(&file)?
+            } else {
+                schema::decode_songs(&std::fs::read_to_string(&file)?)?.0
+            };
+            let total = songs.len();
+            let mut added = 0;
+            for song in songs {
+                if dedupe::find_duplicate_of(&playlist, &song, None).is_none() {
+                    playlist.push(song);
+                    added += 1;
+                }
+            }
+            App::write_playlist(&playlist, true)?;
+            if json {
+                out!("{}", json!({ "imported": added, "skipped": total - added }));
+            } else {
+                out!("Imported {} songs ({} duplicates skipped)", added, total - added);
+            }
+        }
+        PlaylistCommand::Export { file } => {
+            if is_m3u(&file) {
+                write_m3u(&file, &playlist)?;
+            } else {
+                storage::atomic_write(&file, schema::encode_songs(&playlist)?.as_bytes())?;
+            }
+            if json {
+                out!("{}", json!({ "exported": playlist.len(), "path": file }));
+            } else {
+                out!("Exported {} songs to {}", playlist.len(), file.display());
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// Putar di foreground dengan mpv, lalu keluar dengan exit code mpv
+fn play(query: &str, json: bool) -> CliResult {
+    let song = resolve_song(&load()?, query)?;
+    let source = SongSource::of(&song)?;
+
+    if json {
+        out!("{}", json!({ "playing": song.title, "artist": song.artist, "source": source.to_mpv_arg() }));
+    } else {
+        out!("▶ {}", song.title);
+    }
+
+    let status = Process::new("mpv")
+        .args(["--no-video", "--", &source.to_mpv_arg()])
+        .status()
+        .map_err(|e| format!("cannot start mpv: {}", e))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+fn ctl(line: &str, json: bool) -> CliResult {
+    let reply = control::send(line)?;
+    let ok = reply.get("ok").and_then(|ok| ok.as_bool()).unwrap_or(false);
+
+    if json {
+        out!("{}", reply);
+    } else if !ok {
+        let error = reply.get("error").and_then(|e| e.as_str()).unwrap_or("unknown error");
+        eprintln!("terminus: {}", error);
+    } else if let Some(state) = reply.get("state").and_then(|s| s.as_str()) {
+        let title = reply
+            .get("stream_title")
+            .and_then(|t| t.as_str())
+            .or_else(|| reply.get("title").and_then(|t| t.as_str()));
+        match title {
+            Some(title) => out!("{}: {}", state, title),
+            None => out!("{}", state),
+        }
+        out!("volume: {}  queue: {}", reply["volume"], reply["queue"]);
+    } else if let Some(queued) = reply.get("queued").and_then(|q| q.as_str()) {
+        out!("Queued {} ({} in queue)", queued, reply["queue"]);
+    } else if let Some(volume) = reply.get("volume") {
+        out!("volume: {}", volume);
+    }
+    Ok(if ok { 0 } else { 1 })
+}
+
+/// Lagu dari playlist kalau cocok, selain itu path/URL langsung
+pub fn resolve_song(playlist: &[Song], query: &str) -> Result<Song, String> {
+    match find_song(playlist, query) {
+        Some(index) => Ok(playlist[index].clone()),
+        None if query.trim().parse::<usize>().is_ok() => {
+            Err(format!("playlist has only {} songs", playlist.len()))
+        }
+        None => Song::from_input(&default_title(query), "", query).map_err(|e| e.to_string()),
+    }
+}
+
+/// Cari lagu berdasarkan nomor (1-based), judul persis, atau potongan judul yang unik
+pub fn find_song(playlist: &[Song], query: &str) -> Option<usize> {
+    let query = query.trim();
+    if let Ok(number) = query.parse::<usize>() {
+        return (1..=playlist.len()).contains(&number).then(|| number - 1);
+    }
+    let lower = query.to_lowercase();
+    if let Some(index) = playlist.iter().position(|song| song.title.to_lowercase() == lower) {
+        return Some(index);
+    }
+    let mut matches = playlist
+        .iter()
+        .enumerate()
+        .filter(|(_, song)| song.title.to_lowercase().contains(&lower));
+    match (matches.next(), matches.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+fn is_m3u(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("m3u") || ext.eq_ignore_ascii_case("m3u8"))
+}
+
+/// Judul default: nama file tanpa ekstensi, atau URL-nya sendiri
+fn default_title(source: &str) -> String {
+    match SongSource::parse(source) {
+        Ok(SongSource::LocalFile(path)) => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| source.to_string()),
+        _ => source.to_string(),
+    }
+}
@@ -0,0 +1,180 @@
+// src/config.rs
+use std::{fs, io, path::PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::app::expand_tilde;
+use crate::tools::album_art::ArtMode;
+use crate::storage;
+use crate::tools::downloader::Preset;
+
+/// Pengaturan user di `~/.config/terminus/config.json`. Field yang tidak ada
+/// di file memakai nilai default, jadi file lama tetap bisa dibaca.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Kirim desktop notification setiap kali lagu berganti
+    pub notifications: bool,
+    /// Output audio untuk mpv (`--audio-device`); `None` berarti default sistem
+    pub audio_device: Option<String>,
+    /// Cover art di NowPlaying: auto, kitty, sixel, blocks, atau off
+    pub album_art: ArtMode,
+    pub cache: CacheConfig,
+    pub downloads: DownloadConfig,
+    pub scrobble: ScrobbleConfig,
+    /// File yang ada tidak bisa dibaca dan tidak bisa dipindah; jangan ditimpa
+    #[serde(skip)]
+    read_only: bool,
+}
+
+/// Tool Downloads (yt-dlp)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadConfig {
+    /// Folder tujuan; kosong berarti `~/Downloads/terminus`
+    pub dir: Option<String>,
+    /// Jumlah yt-dlp yang boleh berjalan bersamaan
+    pub concurrency: usize,
+    pub preset: Preset,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self { dir: None, concurrency: 2, preset: Preset::default() }
+    }
+}
+
+impl DownloadConfig {
+    pub fn dir(&self) -> PathBuf {
+        match &self.dir {
+            Some(dir) if !dir.trim().is_empty() => expand_tilde(dir),
+            _ => dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("terminus"),
+        }
+    }
+}
+
+/// Cache offline untuk lagu URL (diunduh lewat yt-dlp)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Simpan salinan setiap lagu URL yang diputar, bukan hanya yang di-pin
+    pub auto: bool,
+    /// Batas ukuran cache; file lama yang tidak di-pin dibuang lebih dulu
+    pub max_size_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { auto: false, max_size_mb: 2048 }
+    }
+}
+
+impl CacheConfig {
+    pub fn max_bytes(&self) -> u64 {
+        self.max_size_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrobbleApi {
+    #[default]
+    ListenBrainz,
+    /// Audioscrobbler 2.0 (Last.fm, Libre.fm, ...)
+    LastFm,
+}
+
+/// Pengaturan scrobbling. `url` kosong berarti server resmi dari `api`;
+/// isi dengan server self-hosted atau mock lokal untuk testing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrobbleConfig {
+    pub enabled: bool,
+    pub api: ScrobbleApi,
+    pub url: String,
+    /// Token user ListenBrainz, atau session key (`sk`) untuk Last.fm
+    pub token: String,
+    pub api_key: String,
+    pub api_secret: String,
+}
+
+impl ScrobbleConfig {
+    pub fn endpoint(&self) -> String {
+        let url = self.url.trim().trim_end_matches('/');
+        match (self.api, url.is_empty()) {
+            (ScrobbleApi::ListenBrainz, true) => String::from("https://api.listenbrainz.org"),
+            (ScrobbleApi::LastFm, true) => String::from("https://ws.audioscrobbler.com/2.0"),
+            (_, false) => url.to_string(),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            notifications: true,
+            audio_device: None,
+            album_art: ArtMode::default(),
+            cache: CacheConfig::default(),
+            downloads: DownloadConfig::default(),
+            scrobble: ScrobbleConfig::default(),
+            read_only: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("terminus").join("config.json"))
+    }
+
+    /// Baca config beserta pesan error untuk user kalau file-nya bermasalah.
+    /// File yang tidak bisa di-parse tidak pernah ditimpa (isinya bisa berupa
+    /// token scrobble): dipindah ke `config.json.corrupt-<unix time>`, atau
+    /// kalau tidak bisa dipindah, config ini tidak akan disimpan sama sekali.
+    pub fn load() -> (Self, Option<String>) {
+        let Some(path) = Self::path() else { return (Self::default(), None) };
+        let read_only = Self { read_only: true, ..Self::default() };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return (Self::default(), None),
+            Err(e) => {
+                let message = format!("Cannot read {}: {}; config changes will not be saved", path.display(), e);
+                return (read_only, Some(message));
+            }
+        };
+        let error = match serde_json::from_str(&content) {
+            Ok(config) => return (config, None),
+            Err(e) => e,
+        };
+        let quarantined = storage::quarantine_path(&path);
+        match fs::rename(&path, &quarantined) {
+            Ok(()) => {
+                let message = format!(
+                    "config.json was invalid ({}); moved to {}, using default settings",
+                    error,
+                    quarantined.display()
+                );
+                (Self::default(), Some(message))
+            }
+            Err(e) => {
+                let message = format!(
+                    "config.json is invalid ({}) and could not be moved aside: {}; config changes will not be saved",
+                    error, e
+                );
+                (read_only, Some(message))
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.read_only {
+            return Err("config.json could not be read, refusing to overwrite it".into());
+        }
+        let path = Self::path().ok_or("Config directory not found")?;
+        storage::atomic_write_private(&path, serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+// src/history.rs
+
+/// Satu langkah di history: state sebelum perubahan + deskripsi untuk toast
+struct Snapshot<T> {
+    description: String,
+    state: T,
+}
+
+/// Undo/redo berbasis snapshot. Playlist kecil, jadi menyimpan salinan
+/// utuh per langkah lebih sederhana daripada menyimpan operasi kebalikannya.
+pub struct History<T> {
+    undo_stack: Vec<Snapshot<T>>,
+    redo_stack: Vec<Snapshot<T>>,
+    limit: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Catat state *sebelum* perubahan. Perubahan baru menghapus redo stack.
+    pub fn record(&mut self, description: impl Into<String>, before: T) {
+        self.undo_stack.push(Snapshot {
+            description: description.into(),
+            state: before,
+        });
+        if self.undo_stack.len() > self.limit {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    /// State yang akan dikembalikan oleh `undo` berikutnya
+    pub fn peek_undo(&self) -> Option<&T> {
+        self.undo_stack.last().map(|snapshot| &snapshot.state)
+    }
+
+    pub fn peek_redo(&self) -> Option<&T> {
+        self.redo_stack.last().map(|snapshot| &snapshot.state)
+    }
+
+    /// Kembalikan state sebelumnya; `current` disimpan supaya bisa di-redo.
+    pub fn undo(&mut self, current: T) -> Option<(String, T)> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(Snapshot {
+            description: snapshot.description.clone(),
+            state: current,
+        });
+        Some((snapshot.description, snapshot.state))
+    }
+
+    pub fn redo(&mut self, current: T) -> Option<(String, T)> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(Snapshot {
+            description: snapshot.description.clone(),
+            state: current,
+        });
+        Some((snapshot.description, snapshot.state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_back_and_forth_in_order() {
+        let mut history = History::new(10);
+        history.record("first", 0);
+        history.record("second", 1);
+
+        assert_eq!(history.undo(2), Some((String::from("second"), 1)));
+        assert_eq!(history.undo(1), Some((String::from("first"), 0)));
+        assert_eq!(history.undo(0), None);
+
+        assert_eq!(history.redo(0), Some((String::from("first"), 1)));
+        assert_eq!(history.redo(1), Some((String::from("second"), 2)));
+        assert_eq!(history.redo(2), None);
+        assert_eq!(history.peek_undo(), Some(&1));
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut history = History::new(10);
+        history.record("first", 0);
+        history.undo(1);
+        assert_eq!(history.peek_redo(), Some(&1));
+
+        history.record("other", 0);
+        assert_eq!(history.peek_redo(), None);
+        assert_eq!(history.redo(5), None);
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped_past_the_limit() {
+        let mut history = History::new(100);
+        for i in 0..150 {
+            history.record(format!("edit {}", i), i);
+        }
+
+        let mut undone = Vec::new();
+        while let Some((_, state)) = history.undo(0) {
+            undone.push(state);
+        }
+        assert_eq!(undone.len(), 100);
+        assert_eq!(undone.first(), Some(&149));
+        assert_eq!(undone.last(), Some(&50));
+    }
+}
@@ -0,0 +1,249 @@
+// src/logging.rs
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::storage;
+use crate::tools::date::civil_from_days;
+use crate::widgets::text_input::TextInput;
+
+/// Env var untuk level log kalau `--log-level` tidak diberikan
+pub const LOG_ENV: &str = "TERMINUS_LOG";
+
+/// Ukuran maksimal `terminus.log` sebelum digeser ke `terminus.log.1`
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+const LOG_BACKUPS: usize = 3;
+
+/// Berapa byte terakhir yang dibaca log viewer
+const VIEW_BYTES: u64 = 256 * 1024;
+
+/// `$XDG_STATE_HOME/terminus/terminus.log`
+pub fn log_path() -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(dir.join("terminus").join("terminus.log"))
+}
+
+struct FileLogger {
+    level: LevelFilter,
+    path: PathBuf,
+    file: Mutex<(File, u64)>,
+}
+
+impl FileLogger {
+    fn open(path: &Path) -> io::Result<(File, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok((file, size))
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            format_timestamp(SystemTime::now()),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        let Ok(mut guard) = self.file.lock() else { return };
+        if guard.1 + line.len() as u64 > MAX_LOG_SIZE {
+            // Gagal rotasi berarti tetap menulis ke file lama
+            if storage::rotate_backups(&self.path, LOG_BACKUPS).is_ok()
+                && let Ok(file) = File::create(&self.path)
+            {
+                *guard = (file, 0);
+            }
+        }
+        if guard.0.write_all(line.as_bytes()).is_ok() {
+            guard.1 += line.len() as u64;
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut guard) = self.file.lock() {
+            let _ = guard.0.flush();
+        }
+    }
+}
+
+/// Pasang logger global. Level dari argumen, lalu `TERMINUS_LOG`, default `info`.
+pub fn init(level: Option<LevelFilter>) -> Result<PathBuf, String> {
+    let level = match level {
+        Some(level) => level,
+        None => match std::env::var(LOG_ENV) {
+            Ok(value) => value
+                .parse()
+                .map_err(|_| format!("invalid {} value `{}`", LOG_ENV, value))?,
+            Err(_) => LevelFilter::Info,
+        },
+    };
+    let path = log_path().ok_or("state directory not found")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    }
+    let file = FileLogger::open(&path).map_err(|e| format!("cannot open {}: {}", path.display(), e))?;
+
+    log::set_boxed_logger(Box::new(FileLogger { level, path: path.clone(), file: Mutex::new(file) }))
+        .map_err(|e| e.to_string())?;
+    log::set_max_level(level);
+    Ok(path)
+}
+
+/// `2026-01-31T12:34:56.789Z`, selalu UTC
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let rem = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Satu baris di log viewer
+pub struct LogLine {
+    /// `None` untuk baris lanjutan (pesan multi-baris)
+    pub level: Option<Level>,
+    pub text: String,
+}
+
+/// State layar log viewer
+pub struct LogView {
+    pub lines: Vec<LogLine>,
+    /// Level paling detail yang ditampilkan
+    pub level: Level,
+    /// Filter teks, langsung diterapkan selama diketik
+    pub search: TextInput,
+    pub searching: bool,
+    /// Posisi dari atas daftar yang sudah difilter
+    pub selected: usize,
+    /// Ikuti baris terbaru seperti `tail -f`
+    pub follow: bool,
+    /// Ukuran file saat terakhir dibaca, untuk mendeteksi tulisan baru
+    read_size: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl LogView {
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            level: Level::Trace,
+            search: TextInput::new(),
+            searching: false,
+            selected: 0,
+            follow: true,
+            read_size: None,
+            error: None,
+        }
+    }
+
+    /// Baca ulang ekor file kalau ukurannya berubah
+    pub fn refresh(&mut self) {
+        let Some(path) = log_path() else {
+            self.error = Some(String::from("state directory not found"));
+            return;
+        };
+        let size = fs::metadata(&path).map(|meta| meta.len()).ok();
+        if size.is_some() && size == self.read_size {
+            return;
+        }
+        self.read_size = size;
+        match read_tail(&path, VIEW_BYTES) {
+            Ok(content) => {
+                self.error = None;
+                self.lines = content.lines().map(parse_line).collect();
+            }
+            Err(e) => {
+                self.lines.clear();
+                self.error = Some(format!("{}: {}", path.display(), e));
+            }
+        }
+        if self.follow {
+            self.selected = self.visible().len().saturating_sub(1);
+        }
+    }
+
+    /// Paksa baca ulang pada `refresh` berikutnya
+    pub fn invalidate(&mut self) {
+        self.read_size = None;
+    }
+
+    /// Baris yang lolos filter level dan pencarian
+    pub fn visible(&self) -> Vec<&LogLine> {
+        let search = self.search.value().to_lowercase();
+        self.lines
+            .iter()
+            .filter(|line| line.level.is_none_or(|level| level <= self.level))
+            .filter(|line| search.is_empty() || line.text.to_lowercase().contains(&search))
+            .collect()
+    }
+
+    /// Putar filter: trace → debug → info → warn → error → trace
+    pub fn cycle_level(&mut self) {
+        self.level = match self.level {
+            Level::Trace => Level::Debug,
+            Level::Debug => Level::Info,
+            Level::Info => Level::Warn,
+            Level::Warn => Level::Error,
+            Level::Error => Level::Trace,
+        };
+        self.clamp_selection();
+    }
+
+    pub fn scroll(&mut self, offset: isize) {
+        let len = self.visible().len();
+        let selected = (self.selected as isize + offset).clamp(0, len.saturating_sub(1) as isize) as usize;
+        self.selected = selected;
+        self.follow = len > 0 && selected + 1 == len;
+    }
+
+    pub fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        if self.follow || self.selected >= len {
+            self.selected = len.saturating_sub(1);
+        }
+    }
+}
+
+fn read_tail(path: &Path, bytes: u64) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+    let mut content = String::from_utf8_lossy(&buffer).into_owned();
+    // Buang baris pertama yang kemungkinan terpotong
+    if start > 0
+        && let Some(newline) = content.find('\n')
+    {
+        content.drain(..=newline);
+    }
+    Ok(content)
+}
+
+fn parse_line(line: &str) -> LogLine {
+    let level = line.split_whitespace().nth(1).and_then(|token| token.parse().ok());
+    LogLine { level, text: line.to_string() }
+}
@@ -1,10 +1,21 @@
 mod app;
+mod cli;
+mod config;
+mod storage;
+mod schema;
+mod history;
+mod logging;
+mod toast;
 mod ui;
 mod tools;
+mod widgets;
 
 use std::{io, time::Duration};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -13,49 +24,104 @@ use tui::{
     Terminal,
 };
 
-use crate::app::{App, AppState, MenuItem};
+use clap::Parser;
+
+use crate::app::{App, AppState, MenuItem, MusicPlayerMode};
+use crate::cli::Cli;
+use crate::tools::control::ControlServer;
+use crate::tools::flat_playlist;
 
 fn main() -> Result<(), io::Error> {
+    let cli = Cli::parse();
+    let logging = logging::init(cli.log_level);
+    log::info!("terminus {} started", env!("CARGO_PKG_VERSION"));
+
+    // Subcommand berjalan tanpa TUI
+    if let Some(command) = cli.command {
+        if let Err(e) = &logging {
+            eprintln!("terminus: file logging disabled: {}", e);
+        }
+        std::process::exit(cli::run(command, cli.json));
+    }
+
+    // Dump yt-dlp dibaca sebelum raw mode, karena bisa datang dari stdin
+    let import = cli.import.as_deref().map(flat_playlist::read);
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
+    if let Err(e) = logging {
+        app.set_warning(format!("File logging disabled: {}", e));
+    }
+    if let Some(screen) = cli.screen {
+        app.state = screen.state();
+    }
+    if let Some(result) = import {
+        app.open_import_preview(result);
+    }
     
+    // Instance kedua tetap jalan, hanya tanpa control socket
+    let control = ControlServer::bind().ok();
+
     let tick_rate = Duration::from_millis(100);
-    let res = run_app(&mut terminal, &mut app, tick_rate);
+    let res = run_app(&mut terminal, &mut app, control.as_ref(), tick_rate);
+    app.flush_speed_save();
+    // Hapus cover kitty yang masih tampil
+    let _ = app.album_art.flush(terminal.backend_mut());
 
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
+        log::error!("terminal error: {:?}", err);
         println!("{:?}", err)
     }
+    log::info!("terminus exiting");
 
     Ok(())
 }
 
-fn run_app<B: Backend>(
+fn run_app<B: Backend + io::Write>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    control: Option<&ControlServer>,
     tick_rate: Duration,
 ) -> io::Result<()> {
     loop {
+        app.on_tick();
+        if let Some(control) = control {
+            for request in control.requests.try_iter() {
+                let result = app.apply_control(request.command.clone());
+                request.respond(result);
+            }
+        }
         terminal.draw(|f| ui::draw(f, app))?;
+        // Cover kitty/sixel ditulis langsung ke terminal di atas frame tui
+        if app.album_art.flush(terminal.backend_mut())? {
+            terminal.clear()?;
+        }
 
         if event::poll(tick_rate)? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Paste(text) = &event {
+                app.handle_paste(text);
+            }
+            if let Event::Key(key) = event {
                 match app.state {
                     AppState::MainMenu => {
                         match key.code {
                             KeyCode::Char('q') => return Ok(()),
+                            KeyCode::Char('!') => app.open_messages(),
                             KeyCode::Down => app.next(),
                             KeyCode::Up => app.previous(),
                             KeyCode::Enter => {
@@ -63,7 +129,10 @@ fn run_app<B: Backend>(
                                     MenuItem::MusicPlayer => app.state = AppState::MusicPlayer,
                                     MenuItem::VideoPlayer => app.state = AppState::VideoPlayer,
                                     MenuItem::FileTools => app.state = AppState::FileTools,
+                                    MenuItem::Downloads => app.state = AppState::Downloads,
                                     MenuItem::ComingSoon => app.state = AppState::ComingSoon,
+                                    MenuItem::Settings => app.open_settings(),
+                                    MenuItem::Logs => app.open_logs(),
                                     MenuItem::Quit => return Ok(()),
                                 }
                             }
@@ -71,7 +140,9 @@ fn run_app<B: Backend>(
                         }
                     }
                     AppState::MusicPlayer => {
-                        if key.code == KeyCode::Esc {
+                        // Esc di form, prompt, dan layar lain milik Music Player
+                        // ditangani mode itu sendiri (batal/kembali ke Browse)
+                        if key.code == KeyCode::Esc && app.mp_mode == MusicPlayerMode::Browse {
                             app.state = AppState::MainMenu;
                         } else {
                             app.handle_music_player_input(key);
@@ -96,6 +167,10 @@ fn run_app<B: Backend>(
                             app.state = AppState::MainMenu;
                         }
                     }
+                    AppState::Messages => app.handle_messages_input(key),
+                    AppState::Logs => app.handle_logs_input(key),
+                    AppState::Settings => app.handle_settings_input(key),
+                    AppState::Downloads => app.handle_downloads_input(key),
                 }
             }
         }
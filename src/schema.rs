@@ -0,0 +1,220 @@
+// src/schema.rs
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app::Song;
+use crate::storage;
+
+/// Versi format file playlist yang ditulis oleh build ini.
+///
+/// - 0: array lagu polos (format lama, tanpa envelope)
+/// - 1: `{"version": 1, "songs": [...]}`
+pub const CURRENT_VERSION: u64 = 1;
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a> {
+    version: u64,
+    songs: &'a [Song],
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    songs: Vec<Song>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Bukan JSON yang valid, atau isinya tidak cocok dengan format lagu
+    Parse(String),
+    /// JSON valid tapi bukan array maupun envelope
+    Unrecognized,
+    /// Ditulis oleh terminus yang lebih baru; jangan disentuh
+    TooNew(u64),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Parse(e) => write!(f, "{}", e),
+            SchemaError::Unrecognized => write!(f, "not a terminus playlist"),
+            SchemaError::TooNew(version) => write!(
+                f,
+                "written by a newer terminus (format v{}, this build reads up to v{})",
+                version, CURRENT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+pub fn encode_songs(songs: &[Song]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&EnvelopeRef { version: CURRENT_VERSION, songs })
+}
+
+/// Baca playlist versi apa pun dan migrasikan ke versi sekarang.
+/// Mengembalikan lagu beserta versi asli file-nya.
+pub fn decode_songs(content: &str) -> Result<(Vec<Song>, u64), SchemaError> {
+    let mut value: Value = serde_json::from_str(content).map_err(|e| SchemaError::Parse(e.to_string()))?;
+    let original = version_of(&value)?;
+    if original > CURRENT_VERSION {
+        return Err(SchemaError::TooNew(original));
+    }
+
+    let mut version = original;
+    while version < CURRENT_VERSION {
+        value = migrate(version, value);
+        version += 1;
+    }
+
+    let envelope: Envelope = serde_json::from_value(value).map_err(|e| SchemaError::Parse(e.to_string()))?;
+    Ok((envelope.songs, original))
+}
+
+fn version_of(value: &Value) -> Result<u64, SchemaError> {
+    match value {
+        Value::Array(_) => Ok(0),
+        Value::Object(map) => map
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(SchemaError::Unrecognized),
+        _ => Err(SchemaError::Unrecognized),
+    }
+}
+
+/// Satu langkah migrasi dari `version` ke `version + 1`
+fn migrate(version: u64, value: Value) -> Value {
+    match version {
+        0 => json!({ "version": 1, "songs": value }),
+        _ => value,
+    }
+}
+
+/// Hasil membaca playlist utama dari disk
+pub enum PlaylistLoad {
+    /// File belum ada
+    Missing,
+    Loaded(Vec<Song>),
+    /// File rusak sudah dipindah ke `quarantined`; isinya diambil dari backup
+    Recovered {
+        songs: Vec<Song>,
+        quarantined: PathBuf,
+        backup: PathBuf,
+        error: SchemaError,
+    },
+    /// File rusak sudah dipindah dan tidak ada backup yang bisa dibaca
+    Quarantined { quarantined: PathBuf, error: SchemaError },
+    /// Tidak bisa dibaca tapi juga tidak boleh ditimpa (IO error, versi lebih baru)
+    Unreadable(String),
+}
+
+/// Baca playlist di `path`. File yang tidak bisa di-parse tidak pernah
+/// ditimpa: dipindah ke `<nama>.corrupt-<unix time>` lalu backup
+/// `<nama>.1..N` dicoba satu per satu.
+pub fn load_playlist_file(path: &Path) -> PlaylistLoad {
+    let error = match read_songs(path) {
+        Ok(Some(songs)) => return PlaylistLoad::Loaded(songs),
+        Ok(None) => return PlaylistLoad::Missing,
+        Err(ReadError::Io(e)) => return PlaylistLoad::Unreadable(format!("cannot read {}: {}", path.display(), e)),
+        Err(ReadError::Schema(e @ SchemaError::TooNew(_))) => {
+            return PlaylistLoad::Unreadable(format!("{} {}", path.display(), e));
+        }
+        Err(ReadError::Schema(e)) => e,
+    };
+
+    let quarantined = storage::quarantine_path(path);
+    if let Err(e) = fs::rename(path, &quarantined) {
+        return PlaylistLoad::Unreadable(format!(
+            "{} is corrupt ({}) and could not be moved aside: {}",
+            path.display(),
+            error,
+            e
+        ));
+    }
+
+    for n in 1..=storage::BACKUP_COUNT {
+        let backup = storage::backup_path(path, n);
+        if let Ok(Some(songs)) = read_songs(&backup) {
+            return PlaylistLoad::Recovered { songs, quarantined, backup, error };
+        }
+    }
+    PlaylistLoad::Quarantined { quarantined, error }
+}
+
+/// Baca playlist pendukung (mis. playlist bernama). `Ok(None)` kalau file
+/// belum ada; file rusak dilaporkan tapi dibiarkan di tempatnya.
+pub fn read_songs(path: &Path) -> Result<Option<Vec<Song>>, ReadError> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ReadError::Io(e)),
+    };
+    let content = std::str::from_utf8(&bytes).map_err(|e| ReadError::Schema(SchemaError::Parse(e.to_string())))?;
+    let (songs, _) = decode_songs(content).map_err(ReadError::Schema)?;
+    Ok(Some(songs))
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Schema(SchemaError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Schema(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V0: &str = r#"[{"title": "Song", "artist": "Artist", "url": "https://example.com/a", "path": null}]"#;
+
+    #[test]
+    fn bare_array_is_migrated_from_v0() {
+        let (songs, version) = decode_songs(V0).unwrap();
+        assert_eq!(version, 0);
+        assert_eq!(songs.len(), 1);
+        assert_eq!(songs[0].title, "Song");
+        assert_eq!(songs[0].speed, None);
+        assert!(!songs[0].radio);
+    }
+
+    #[test]
+    fn current_envelope_round_trips() {
+        let (songs, _) = decode_songs(V0).unwrap();
+        let encoded = encode_songs(&songs).unwrap();
+        assert_eq!(decode_songs(&encoded), Ok((songs, CURRENT_VERSION)));
+    }
+
+    #[test]
+    fn empty_playlists_decode_in_both_formats() {
+        assert_eq!(decode_songs("[]"), Ok((Vec::new(), 0)));
+        assert_eq!(decode_songs(r#"{"version": 1, "songs": []}"#), Ok((Vec::new(), 1)));
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let content = format!(r#"{{"version": {}, "songs": []}}"#, CURRENT_VERSION + 1);
+        assert_eq!(decode_songs(&content), Err(SchemaError::TooNew(CURRENT_VERSION + 1)));
+    }
+
+    #[test]
+    fn unrecognized_and_invalid_content() {
+        assert_eq!(decode_songs(r#"{"songs": []}"#), Err(SchemaError::Unrecognized));
+        assert_eq!(decode_songs("42"), Err(SchemaError::Unrecognized));
+        assert!(matches!(decode_songs("[{"), Err(SchemaError::Parse(_))));
+        assert!(matches!(decode_songs(r#"[{"title": "no other fields"}]"#), Err(SchemaError::Parse(_))));
+    }
+}
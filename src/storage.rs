@@ -0,0 +1,216 @@
+// src/storage.rs
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Jumlah backup `playlist.json.1..N` yang disimpan
+pub const BACKUP_COUNT: usize = 5;
+
+/// Tulis file lewat temp file di folder yang sama lalu `rename`, jadi file
+/// lama tetap utuh kalau proses mati di tengah penulisan.
+pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_via_temp(path, contents, 0o666)
+}
+
+/// Seperti `atomic_write`, tapi hanya bisa dibaca pemiliknya (0600); untuk
+/// file yang berisi token atau password
+pub fn atomic_write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_via_temp(path, contents, 0o600)
+}
+
+fn write_via_temp(path: &Path, contents: &[u8], mode: u32) -> io::Result<()> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let tmp = dir.join(format!(".{}.tmp.{}", file_name.to_string_lossy(), std::process::id()));
+
+    let result = (|| {
+        // Mode hanya berlaku untuk file baru, jadi sisa temp lama dibuang dulu
+        let _ = fs::remove_file(&tmp);
+        let mut file = OpenOptions::new().write(true).create_new(true).mode(mode).open(&tmp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        // Pastikan entri rename juga sampai ke disk
+        File::open(dir)?.sync_all()
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Nama untuk memindahkan file rusak: `<nama>.corrupt-<unix time>`, tanpa
+/// menimpa file rusak lain dari detik yang sama
+pub fn quarantine_path(path: &Path) -> PathBuf {
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".corrupt-{}", stamp));
+    let mut candidate = PathBuf::from(&name);
+    let mut n = 1;
+    while candidate.exists() {
+        candidate = PathBuf::from(format!("{}-{}", name.to_string_lossy(), n));
+        n += 1;
+    }
+    candidate
+}
+
+/// Path backup ke-`n`, mis. `playlist.json.1`
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Geser `file.1..N-1` ke `file.2..N` lalu salin isi sekarang ke `file.1`.
+/// Tidak melakukan apa-apa kalau file belum ada.
+pub fn rotate_backups(path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+    for n in (1..keep).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, n + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+/// Advisory lock untuk mendeteksi instance kedua. Lock dilepas otomatis
+/// saat proses keluar (termasuk crash), jadi tidak ada lock basi.
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// `Ok(None)` kalau lock sedang dipegang proses lain
+    pub fn acquire(path: &Path) -> io::Result<Option<Self>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(path)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(fs::TryLockError::WouldBlock) => Ok(None),
+            Err(fs::TryLockError::Error(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("terminus-storage-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn atomic_write_replaces_without_leftovers() {
+        let dir = temp_dir("write");
+        let path = dir.join("playlist.json");
+        atomic_write(&path, b"old").unwrap();
+        atomic_write(&path, b"new").unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let files = names(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(content, "new");
+        assert_eq!(files, ["playlist.json"]);
+    }
+
+    #[test]
+    fn failed_atomic_write_keeps_the_target_and_removes_temp() {
+        let dir = temp_dir("fail");
+        // Rename file ke atas folder yang tidak kosong selalu gagal
+        let path = dir.join("taken");
+        fs::create_dir_all(path.join("inside")).unwrap();
+        let result = atomic_write(&path, b"data");
+        let files = names(&dir);
+        let still_dir = path.join("inside").is_dir();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+        assert!(still_dir);
+        assert_eq!(files, ["taken"]);
+    }
+
+    #[test]
+    fn private_write_is_owner_only() {
+        let dir = temp_dir("private");
+        let path = dir.join("config.json");
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        atomic_write_private(&path, b"{\"token\": \"secret\"}").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn rotation_shifts_backups_and_drops_the_oldest() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("playlist.json");
+        for version in 1..=4 {
+            fs::write(&path, format!("v{}", version)).unwrap();
+            rotate_backups(&path, 3).unwrap();
+        }
+        let backups: Vec<String> = (1..=3).map(|n| fs::read_to_string(backup_path(&path, n)).unwrap()).collect();
+        let files = names(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(backups, ["v4", "v3", "v2"]);
+        assert_eq!(files, ["playlist.json", "playlist.json.1", "playlist.json.2", "playlist.json.3"]);
+    }
+
+    #[test]
+    fn rotation_without_file_or_keep_does_nothing() {
+        let dir = temp_dir("rotate-none");
+        let path = dir.join("playlist.json");
+        rotate_backups(&path, 3).unwrap();
+        fs::write(&path, "v1").unwrap();
+        rotate_backups(&path, 0).unwrap();
+        let files = names(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, ["playlist.json"]);
+    }
+
+    #[test]
+    fn second_lock_fails_until_the_first_is_released() {
+        let dir = temp_dir("lock");
+        let path = dir.join("playlist.json.lock");
+        let first = InstanceLock::acquire(&path).unwrap();
+        let second = InstanceLock::acquire(&path).unwrap();
+        let held = first.is_some() && second.is_none();
+        drop(first);
+        let after_release = InstanceLock::acquire(&path).unwrap().is_some();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(held);
+        assert!(after_release);
+    }
+}
@@ -0,0 +1,115 @@
+// src/toast.rs
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant, SystemTime},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Error tampil lebih lama supaya sempat terbaca
+    fn timeout(self) -> Duration {
+        match self {
+            Level::Info => Duration::from_secs(4),
+            Level::Warn => Duration::from_secs(6),
+            Level::Error => Duration::from_secs(10),
+        }
+    }
+
+    fn log_level(self) -> log::Level {
+        match self {
+            Level::Info => log::Level::Info,
+            Level::Warn => log::Level::Warn,
+            Level::Error => log::Level::Error,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Level::Info => "ℹ",
+            Level::Warn => "⚠",
+            Level::Error => "✖",
+        }
+    }
+}
+
+pub struct Toast {
+    pub level: Level,
+    pub message: String,
+    pub time: SystemTime,
+    /// Berapa kali pesan yang sama muncul berturut-turut
+    pub repeats: u32,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub fn is_active(&self) -> bool {
+        self.shown_at.elapsed() < self.level.timeout()
+    }
+}
+
+/// Semua pesan untuk user: yang terbaru tampil di status bar sampai
+/// kedaluwarsa, sisanya bisa dibaca lagi di layar Messages.
+pub struct Toasts {
+    entries: VecDeque<Toast>,
+    limit: usize,
+}
+
+impl Toasts {
+    pub fn new(limit: usize) -> Self {
+        Self { entries: VecDeque::new(), limit }
+    }
+
+    /// Tambah pesan; semua toast ikut dicatat di file log
+    pub fn push(&mut self, level: Level, message: impl Into<String>) {
+        let message = message.into();
+        log::log!(level.log_level(), "{}", message);
+        // Error yang berulang (mis. tiap poll) cukup satu entri
+        if let Some(last) = self.entries.back_mut()
+            && last.level == level
+            && last.message == message
+        {
+            last.repeats += 1;
+            last.time = SystemTime::now();
+            last.shown_at = Instant::now();
+            return;
+        }
+        self.entries.push_back(Toast {
+            level,
+            message,
+            time: SystemTime::now(),
+            repeats: 1,
+            shown_at: Instant::now(),
+        });
+        if self.entries.len() > self.limit {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Toast terbaru kalau belum kedaluwarsa
+    pub fn current(&self) -> Option<&Toast> {
+        self.entries.back().filter(|toast| toast.is_active())
+    }
+
+    /// Dari yang terbaru
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,503 @@
+// src/tools/album_art.rs
+use std::{
+    cell::Cell,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+use crossterm::{cursor::MoveTo, queue, terminal};
+use serde::{Deserialize, Serialize};
+use tui::layout::Rect;
+
+/// Cara menampilkan cover di config; `auto` memilih dari jenis terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtMode {
+    #[default]
+    Auto,
+    Kitty,
+    Sixel,
+    /// Karakter `▀` dengan warna RGB, jalan di terminal mana pun
+    Blocks,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+    Blocks,
+}
+
+impl ArtMode {
+    pub fn protocol(self) -> Option<Protocol> {
+        match self {
+            ArtMode::Auto => Some(detect()),
+            ArtMode::Kitty => Some(Protocol::Kitty),
+            ArtMode::Sixel => Some(Protocol::Sixel),
+            ArtMode::Blocks => Some(Protocol::Blocks),
+            ArtMode::Off => None,
+        }
+    }
+}
+
+/// Tebak dukungan grafis dari environment. Query terminal (DA1) tidak
+/// dipakai karena jawabannya akan bercampur dengan input keyboard.
+fn detect() -> Protocol {
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+    // tmux butuh passthrough khusus untuk kedua protokol
+    if env::var_os("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+        Protocol::Blocks
+    } else if env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || matches!(program.as_str(), "WezTerm" | "ghostty")
+    {
+        Protocol::Kitty
+    } else if ["foot", "mlterm", "sixel", "contour", "yaft"].iter().any(|name| term.contains(name))
+        || matches!(program.as_str(), "iTerm.app" | "mintty")
+    {
+        Protocol::Sixel
+    } else {
+        Protocol::Blocks
+    }
+}
+
+/// Gambar RGB 8-bit
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    rgb: Vec<u8>,
+}
+
+impl Image {
+    /// Pixel RGB berurutan per baris, tanpa padding
+    pub fn rgb(&self) -> &[u8] {
+        &self.rgb
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> (u8, u8, u8) {
+        let i = ((y * self.width + x) * 3) as usize;
+        (self.rgb[i], self.rgb[i + 1], self.rgb[i + 2])
+    }
+
+    /// Perkecil dengan rata-rata area (box filter), atau perbesar nearest-neighbour
+    pub fn resize(&self, width: u32, height: u32) -> Image {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+        for y in 0..height {
+            let y0 = y * self.height / height;
+            let y1 = ((y + 1) * self.height / height).max(y0 + 1);
+            for x in 0..width {
+                let x0 = x * self.width / width;
+                let x1 = ((x + 1) * self.width / width).max(x0 + 1);
+                let mut sum = [0u32; 3];
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        let (r, g, b) = self.pixel(sx, sy);
+                        sum[0] += r as u32;
+                        sum[1] += g as u32;
+                        sum[2] += b as u32;
+                    }
+                }
+                let count = (x1 - x0) * (y1 - y0);
+                rgb.extend(sum.iter().map(|channel| (channel / count) as u8));
+            }
+        }
+        Image { width, height, rgb }
+    }
+
+    /// Jumlah kolom dan baris cell terbesar yang muat di `area` tanpa
+    /// mengubah rasio gambar, dengan ukuran cell dalam pixel
+    pub fn fit(&self, area: Rect, (cell_w, cell_h): (u32, u32)) -> (u16, u16) {
+        let scale = f64::min(
+            (area.width as u32 * cell_w) as f64 / self.width as f64,
+            (area.height as u32 * cell_h) as f64 / self.height as f64,
+        );
+        let cols = ((self.width as f64 * scale / cell_w as f64) as u16).clamp(1, area.width.max(1));
+        let rows = ((self.height as f64 * scale / cell_h as f64) as u16).clamp(1, area.height.max(1));
+        (cols, rows)
+    }
+}
+
+/// Ukuran satu cell terminal dalam pixel. Kalau terminal tidak melaporkan
+/// ukuran pixel-nya, pakai 8x16 (rasio umum font monospace).
+pub fn cell_size() -> (u32, u32) {
+    match terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns).max(1) as u32,
+            (size.height / size.rows).max(1) as u32,
+        ),
+        _ => (8, 16),
+    }
+}
+
+/// Ukuran maksimum hasil decode; cukup untuk area NowPlaying di layar besar
+const DECODE_SIZE: u32 = 512;
+
+/// Nama file cover yang umum di folder album, tanpa ekstensi
+const COVER_NAMES: &[&str] = &["cover", "folder", "front", "album", "albumart", "albumartsmall"];
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "bmp"];
+
+/// Cover untuk file lokal: gambar yang tertanam di file, lalu gambar di
+/// folder yang sama. `Ok(None)` kalau tidak ada sama sekali. Dipakai juga
+/// oleh desktop notification.
+pub fn find(path: &Path) -> Result<Option<Image>, String> {
+    if let Some(image) = decode(path)? {
+        return Ok(Some(image));
+    }
+    match path.parent().and_then(folder_image) {
+        Some(cover) => decode(&cover),
+        None => Ok(None),
+    }
+}
+
+fn folder_image(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| IMAGE_EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+        })
+        .collect();
+    images.sort();
+    let stem = |path: &PathBuf| path.file_stem().map(|stem| stem.to_string_lossy().to_lowercase()).unwrap_or_default();
+    COVER_NAMES
+        .iter()
+        .find_map(|name| images.iter().find(|path| stem(path) == *name).cloned())
+        .or_else(|| images.into_iter().next())
+}
+
+/// Decode frame video pertama (cover tertanam, atau file gambar) lewat
+/// ffmpeg ke PPM, sudah diperkecil ke `DECODE_SIZE`
+fn decode(path: &Path) -> Result<Option<Image>, String> {
+    let scale = format!(
+        "scale={0}:{0}:force_original_aspect_ratio=decrease",
+        DECODE_SIZE
+    );
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-i"])
+        .arg(path)
+        .args(["-map", "0:v:0", "-frames:v", "1", "-vf", &scale, "-f", "image2pipe", "-c:v", "ppm", "-"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => String::from("ffmpeg is not installed"),
+            _ => format!("cannot run ffmpeg: {}", e),
+        })?;
+    // File audio tanpa stream gambar membuat ffmpeg gagal; itu bukan error
+    if !output.status.success() || output.stdout.is_empty() {
+        return Ok(None);
+    }
+    parse_ppm(&output.stdout).map(Some).ok_or_else(|| String::from("unexpected ffmpeg output"))
+}
+
+/// Parse PPM biner (`P6`) dengan nilai maksimum 255
+fn parse_ppm(data: &[u8]) -> Option<Image> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while fields.len() < 4 {
+        while data.get(pos)?.is_ascii_whitespace() {
+            pos += 1;
+        }
+        let start = pos;
+        while !data.get(pos)?.is_ascii_whitespace() {
+            pos += 1;
+        }
+        fields.push(std::str::from_utf8(&data[start..pos]).ok()?);
+    }
+    // Tepat satu whitespace sebelum data pixel
+    pos += 1;
+    if fields[0] != "P6" || fields[3] != "255" {
+        return None;
+    }
+    let (width, height): (u32, u32) = (fields[1].parse().ok()?, fields[2].parse().ok()?);
+    // Ukuran dari header tidak dipercaya begitu saja; hitung di usize tanpa overflow
+    let len = (width as usize).checked_mul(height as usize)?.checked_mul(3)?;
+    let rgb = data.get(pos..pos.checked_add(len)?)?.to_vec();
+    Some(Image { width, height, rgb })
+}
+
+/// ID gambar kitty milik terminus, supaya bisa dihapus tanpa menyentuh gambar lain
+const KITTY_IMAGE_ID: u32 = 7_350;
+
+/// Cover lagu di layar NowPlaying
+pub struct AlbumArt {
+    /// `None` kalau dimatikan di config
+    pub protocol: Option<Protocol>,
+    /// File lagu yang cover-nya sedang ditampilkan atau dicari
+    source: Option<PathBuf>,
+    pub image: Option<Image>,
+    pub loading: bool,
+    pub error: Option<String>,
+    rx: Option<Receiver<Result<Option<Image>, String>>>,
+    /// Naik setiap kali `image` berganti
+    generation: u64,
+    /// Area (cell) untuk cover, diisi ui setiap kali NowPlaying digambar
+    pub area: Cell<Option<Rect>>,
+    /// Gambar kitty/sixel yang sudah ada di layar
+    drawn: Option<(Rect, u64)>,
+}
+
+impl AlbumArt {
+    pub fn new(mode: ArtMode) -> Self {
+        Self {
+            protocol: mode.protocol(),
+            source: None,
+            image: None,
+            loading: false,
+            error: None,
+            rx: None,
+            generation: 0,
+            area: Cell::new(None),
+            drawn: None,
+        }
+    }
+
+    /// Ganti lagu yang cover-nya ditampilkan; dicari di thread sendiri
+    pub fn show(&mut self, source: Option<PathBuf>) {
+        if self.protocol.is_none() || self.source == source {
+            return;
+        }
+        self.source = source.clone();
+        self.image = None;
+        self.error = None;
+        self.generation += 1;
+        self.loading = source.is_some();
+        self.rx = source.map(|path| {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = tx.send(find(&path));
+            });
+            rx
+        });
+    }
+
+    pub fn poll(&mut self) {
+        let Some(rx) = &self.rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(String::from("cover lookup stopped unexpectedly")),
+        };
+        self.rx = None;
+        self.loading = false;
+        match result {
+            Ok(image) => {
+                self.image = image;
+                self.generation += 1;
+            }
+            Err(e) => {
+                if let Some(source) = &self.source {
+                    log::warn!("cover art for {}: {}", source.display(), e);
+                }
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Tulis gambar kitty/sixel setelah tui selesai menggambar frame, karena
+    /// tui hanya tahu cell teks. Hasilnya `true` kalau layar harus digambar
+    /// ulang penuh untuk menghapus sixel lama (sixel menimpa cell, dan tui
+    /// tidak tahu cell itu sudah berubah).
+    pub fn flush(&mut self, out: &mut impl Write) -> io::Result<bool> {
+        let area = self.area.take();
+        let Some(protocol) = self.protocol.filter(|protocol| *protocol != Protocol::Blocks) else {
+            return Ok(false);
+        };
+        let wanted = area.filter(|_| self.image.is_some()).map(|area| (area, self.generation));
+        if wanted == self.drawn {
+            return Ok(false);
+        }
+        if self.drawn.take().is_some() {
+            match protocol {
+                Protocol::Kitty => write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID)?,
+                // Gambar baru ditulis setelah frame berikutnya
+                _ => return Ok(true),
+            }
+        }
+
+        if let (Some((area, _)), Some(image)) = (wanted, &self.image) {
+            let cell = cell_size();
+            let (cols, rows) = image.fit(area, cell);
+            let x = area.x + (area.width - cols) / 2;
+            let y = area.y + (area.height - rows) / 2;
+            queue!(out, MoveTo(x, y))?;
+            match protocol {
+                Protocol::Kitty => write_kitty(out, image, cols, rows)?,
+                _ => write_sixel(out, &image.resize(cols as u32 * cell.0, rows as u32 * cell.1))?,
+            }
+            self.drawn = wanted;
+        }
+        out.flush()?;
+        Ok(false)
+    }
+}
+
+/// Kitty graphics protocol: pixel RGB mentah dalam base64, dipotong per
+/// 4096 byte, lalu kitty sendiri yang menskalakan ke `cols` x `rows` cell
+fn write_kitty(out: &mut impl Write, image: &Image, cols: u16, rows: u16) -> io::Result<()> {
+    let payload = base64(&image.rgb);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=24,s={},v={},c={},r={},i={},C=1,q=2,m={};",
+                image.width, image.height, cols, rows, KITTY_IMAGE_ID, more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+    Ok(())
+}
+
+/// Sixel dengan palet tetap 6x6x6 (216 warna), cukup untuk cover kecil
+fn write_sixel(out: &mut impl Write, image: &Image) -> io::Result<()> {
+    let level = |value: u8| (value as u32 * 5 + 127) / 255;
+    let colors: Vec<u8> = (0..image.height)
+        .flat_map(|y| (0..image.width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let (r, g, b) = image.pixel(x, y);
+            (level(r) * 36 + level(g) * 6 + level(b)) as u8
+        })
+        .collect();
+
+    let mut data = format!("\x1bP0;1;0q\"1;1;{};{}", image.width, image.height);
+    for index in 0..216u32 {
+        let percent = |level: u32| level * 100 / 5;
+        data.push_str(&format!(
+            "#{};2;{};{};{}",
+            index,
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        ));
+    }
+    let width = image.width as usize;
+    for band in (0..image.height as usize).step_by(6) {
+        let rows = band..(band + 6).min(image.height as usize);
+        let mut used = [false; 216];
+        for y in rows.clone() {
+            for &color in &colors[y * width..(y + 1) * width] {
+                used[color as usize] = true;
+            }
+        }
+        for color in (0..216).filter(|color| used[*color]) {
+            data.push_str(&format!("#{}", color));
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = rows
+                    .clone()
+                    .enumerate()
+                    .filter(|(_, y)| colors[y * width + x] as usize == color)
+                    .fold(0u8, |bits, (dy, _)| bits | (1 << dy));
+                let sixel = (63 + bits) as char;
+                run = match run {
+                    Some((current, count)) if current == sixel => Some((current, count + 1)),
+                    Some((current, count)) => {
+                        push_run(&mut data, current, count);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((current, count)) = run {
+                push_run(&mut data, current, count);
+            }
+            data.push('$');
+        }
+        data.push('-');
+    }
+    data.push_str("\x1b\\");
+    out.write_all(data.as_bytes())
+}
+
+fn push_run(data: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        data.push_str(&format!("!{}{}", count, sixel));
+    } else {
+        data.extend(std::iter::repeat_n(sixel, count));
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_matches_rfc_4648_vectors() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn parses_binary_ppm() {
+        let mut data = b"P6\n2 1\n255\n".to_vec();
+        data.extend_from_slice(&[255, 0, 0, 0, 0, 255]);
+        let image = parse_ppm(&data).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.pixel(0, 0), (255, 0, 0));
+        assert_eq!(image.pixel(1, 0), (0, 0, 255));
+    }
+
+    #[test]
+    fn rejects_truncated_or_bogus_ppm() {
+        let mut data = b"P6\n2 1\n255\n".to_vec();
+        data.extend_from_slice(&[255, 0, 0, 0, 0]);
+        assert!(parse_ppm(&data).is_none());
+        assert!(parse_ppm(b"P6\n2 1").is_none());
+        assert!(parse_ppm(b"P5\n1 1\n255\n\x00").is_none());
+        assert!(parse_ppm(b"P6\n4294967295 4294967295\n255\n\x00\x00\x00").is_none());
+    }
+
+    #[test]
+    fn fit_keeps_aspect_ratio_with_tall_cells() {
+        let image = |width, height| Image { width, height, rgb: Vec::new() };
+        let area = |width, height| Rect::new(0, 0, width, height);
+
+        // 2:1 dengan cell 8x16: 20 kolom x 5 baris = 160x80 pixel
+        assert_eq!(image(200, 100).fit(area(20, 20), (8, 16)), (20, 5));
+        // Persegi dengan cell 10x20 tetap persegi: 40x20 cell = 400x400 pixel
+        assert_eq!(image(100, 100).fit(area(40, 40), (10, 20)), (40, 20));
+        // Gambar tinggi dibatasi oleh jumlah baris
+        assert_eq!(image(100, 300).fit(area(30, 10), (8, 16)), (6, 10));
+        // Area kosong tetap menghasilkan minimal satu cell
+        assert_eq!(image(100, 100).fit(area(0, 0), (8, 16)), (1, 1));
+    }
+}
@@ -0,0 +1,70 @@
+// src/tools/audio_device.rs
+use std::{
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+use serde::Deserialize;
+
+use crate::tools::mpv_ipc;
+
+/// Satu output audio menurut mpv, mis. `pulse/alsa_output.pci-0000_00_1f.3.hdmi-stereo`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AudioDevice {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Nama device bawaan mpv: ikuti default sistem
+pub const AUTO: &str = "auto";
+
+/// Cari daftar device di thread terpisah. Kalau mpv sedang jalan, daftarnya
+/// diambil lewat IPC (`audio-device-list`); kalau tidak, dari
+/// `mpv --audio-device=help`, yang bisa makan waktu saat probing PulseAudio.
+pub fn spawn_scan(playing: bool) -> Receiver<Result<Vec<AudioDevice>, String>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = if playing { from_ipc().or_else(|_| from_help()) } else { from_help() };
+        let _ = tx.send(result);
+    });
+    rx
+}
+
+fn from_ipc() -> Result<Vec<AudioDevice>, String> {
+    let list = mpv_ipc::get_property("audio-device-list").map_err(|e| e.to_string())?;
+    serde_json::from_value(list).map_err(|e| format!("unexpected audio-device-list: {}", e))
+}
+
+fn from_help() -> Result<Vec<AudioDevice>, String> {
+    let output = Command::new("mpv")
+        .args(["--no-config", "--audio-device=help"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| format!("cannot run mpv: {}", e))?;
+    let devices = parse_help(&String::from_utf8_lossy(&output.stdout));
+    if devices.is_empty() {
+        return Err(String::from("mpv reported no audio devices"));
+    }
+    Ok(devices)
+}
+
+/// Parse output `--audio-device=help`:
+///
+/// ```text
+/// List of detected audio devices:
+///   'auto' (Autoselect device)
+///   'pulse/alsa_output.usb-headset' (USB Headset Analog Stereo)
+/// ```
+pub fn parse_help(output: &str) -> Vec<AudioDevice> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix('\'')?;
+            let (name, rest) = rest.split_once('\'')?;
+            let description = rest.trim().trim_start_matches('(').trim_end_matches(')');
+            Some(AudioDevice { name: name.to_string(), description: description.to_string() })
+        })
+        .collect()
+}
@@ -0,0 +1,517 @@
+// src/tools/cache.rs
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::OsString,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::Song;
+use crate::storage;
+use crate::tools::dedupe;
+use crate::tools::source::SongSource;
+
+/// Satu lagu di cache offline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Nama file di folder cache; `None` kalau di-pin tapi belum terunduh
+    pub file: Option<String>,
+    pub size: u64,
+    /// Unix timestamp terakhir diputar atau diunduh, untuk LRU
+    pub last_used: u64,
+    /// Lagu yang di-pin tidak pernah dibuang oleh batas ukuran
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Index {
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Jumlah yt-dlp cache yang boleh berjalan bersamaan; sisanya antre
+const MAX_RUNNING: usize = 2;
+
+/// Satu yt-dlp yang sedang mengunduh ke folder kerjanya sendiri
+struct Download {
+    key: String,
+    /// `.<hash>.download`, dihapus setelah selesai, gagal, atau dibatalkan
+    work: PathBuf,
+    child: Child,
+}
+
+/// Hasil download ke event loop
+#[derive(Debug)]
+pub enum CacheEvent {
+    Finished { key: String, size: u64 },
+    Failed { key: String, error: String },
+}
+
+impl CacheEvent {
+    pub fn key(&self) -> &str {
+        match self {
+            CacheEvent::Finished { key, .. } | CacheEvent::Failed { key, .. } => key,
+        }
+    }
+}
+
+/// Audio lagu URL yang diunduh lewat yt-dlp supaya bisa diputar tanpa
+/// jaringan. Kuncinya URL kanonik (`dedupe::canonical_url`), jadi link
+/// YouTube yang sama dalam bentuk lain tetap memakai satu file.
+pub struct AudioCache {
+    dir: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    /// Kunci yang menunggu giliran diunduh
+    queue: VecDeque<String>,
+    running: Vec<Download>,
+    /// `PATH` untuk mencari yt-dlp; `None` berarti `PATH` proses ini
+    search_path: Option<OsString>,
+}
+
+impl AudioCache {
+    /// `$XDG_CACHE_HOME/terminus/audio`
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir().unwrap_or_else(std::env::temp_dir).join("terminus").join("audio")
+    }
+
+    /// Buka cache di `dir`. Entri yang file-nya sudah hilang dari disk
+    /// dilupakan, kecuali pin-nya.
+    pub fn open(dir: PathBuf) -> Self {
+        let mut index: Index = fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        index.entries.retain(|_, entry| {
+            if entry.file.as_ref().is_some_and(|file| !dir.join(file).is_file()) {
+                entry.file = None;
+                entry.size = 0;
+            }
+            entry.file.is_some() || entry.pinned
+        });
+        Self { dir, entries: index.entries, queue: VecDeque::new(), running: Vec::new(), search_path: None }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let index = Index { entries: self.entries.clone() };
+        let json = serde_json::to_string_pretty(&index).map_err(io::Error::other)?;
+        storage::atomic_write(&self.dir.join("index.json"), json.as_bytes())
+    }
+
+    /// Kunci cache untuk lagu yang bisa di-cache: URL biasa atau halaman
+    /// yt-dlp. File lokal dan radio/live stream tidak punya kunci.
+    pub fn key(song: &Song) -> Option<String> {
+        if song.radio || song.path.as_deref().is_some_and(|path| !path.trim().is_empty()) {
+            return None;
+        }
+        match SongSource::parse(&song.url).ok()? {
+            SongSource::HttpUrl(_) | SongSource::ResolvablePage(_) => Some(dedupe::canonical_url(&song.url)),
+            SongSource::LocalFile(_) | SongSource::Stream(_) => None,
+        }
+    }
+
+    /// File cache untuk kunci ini, kalau sudah lengkap terunduh
+    pub fn cached_path(&self, key: &str) -> Option<PathBuf> {
+        let file = self.entries.get(key)?.file.as_ref()?;
+        Some(self.dir.join(file)).filter(|path| path.is_file())
+    }
+
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.entries.get(key).is_some_and(|entry| entry.pinned)
+    }
+
+    /// Sedang diunduh atau masih antre
+    pub fn is_downloading(&self, key: &str) -> bool {
+        self.queue.iter().any(|queued| queued == key) || self.running.iter().any(|download| download.key == key)
+    }
+
+    /// Tandai baru saja dipakai (untuk urutan LRU)
+    pub fn touch(&mut self, key: &str) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.last_used = now();
+        }
+    }
+
+    /// Pin atau lepas pin. Lepas pin tidak menghapus file; file itu hanya
+    /// kembali ikut antrean LRU.
+    pub fn set_pinned(&mut self, key: &str, pinned: bool) {
+        if pinned {
+            let entry = self.entries.entry(key.to_string()).or_default();
+            entry.pinned = true;
+            if entry.last_used == 0 {
+                entry.last_used = now();
+            }
+        } else if let Some(entry) = self.entries.get_mut(key) {
+            entry.pinned = false;
+            if entry.file.is_none() {
+                self.entries.remove(key);
+            }
+        }
+    }
+
+    /// Masukkan ke antrean download kalau belum ada di cache atau sedang
+    /// diunduh. Kuncinya sendiri adalah URL yang diunduh; yt-dlp baru
+    /// dijalankan di `poll`, paling banyak `MAX_RUNNING` sekaligus.
+    pub fn request(&mut self, key: &str) -> bool {
+        if self.is_downloading(key) || self.cached_path(key).is_some() {
+            return false;
+        }
+        self.queue.push_back(key.to_string());
+        true
+    }
+
+    /// Unduh ulang lagu yang di-pin tapi belum punya file (mis. cache dihapus)
+    pub fn resume_pinned(&mut self) -> usize {
+        let missing: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.pinned && entry.file.is_none())
+            .map(|(key, _)| key.clone())
+            .collect();
+        missing.iter().filter(|key| self.request(key)).count()
+    }
+
+    /// Ambil hasil download yang sudah selesai, catat ke index, lalu jalankan
+    /// yang antre sampai batas `MAX_RUNNING`
+    pub fn poll(&mut self) -> Vec<CacheEvent> {
+        let mut events = Vec::new();
+        let mut index = 0;
+        while index < self.running.len() {
+            let status = match self.running[index].child.try_wait() {
+                Ok(None) => {
+                    index += 1;
+                    continue;
+                }
+                Ok(Some(status)) => Ok(status),
+                Err(e) => Err(format!("cannot wait for yt-dlp: {}", e)),
+            };
+            let download = self.running.remove(index);
+            let result = status.and_then(|status| finish(&download, &self.dir, status));
+            let _ = fs::remove_dir_all(&download.work);
+            match result {
+                Ok(size) => {
+                    let entry = self.entries.entry(download.key.clone()).or_default();
+                    entry.file = Some(file_name(&download.key));
+                    entry.size = size;
+                    entry.last_used = now();
+                    events.push(CacheEvent::Finished { key: download.key, size });
+                }
+                Err(error) => events.push(CacheEvent::Failed { key: download.key, error }),
+            }
+        }
+
+        while self.running.len() < MAX_RUNNING
+            && let Some(key) = self.queue.pop_front()
+        {
+            match self.start(&key) {
+                Ok(download) => self.running.push(download),
+                Err(error) => events.push(CacheEvent::Failed { key, error }),
+            }
+        }
+        events
+    }
+
+    /// Jalankan yt-dlp untuk audio terbaik ke `<hash>.<ext>` di folder kerja.
+    /// Stderr ditulis ke file di folder itu, jadi tidak ada pipe yang perlu
+    /// dibaca thread lain.
+    fn start(&self, url: &str) -> Result<Download, String> {
+        let stem = file_stem(url);
+        let work = self.dir.join(format!(".{}.download", stem));
+        let stderr = fs::create_dir_all(&work)
+            .and_then(|_| File::create(work.join("stderr.log")))
+            .map_err(|e| format!("cannot create {}: {}", work.display(), e))?;
+
+        let mut command = Command::new("yt-dlp");
+        command
+            .args(["--no-playlist", "--no-progress", "--quiet", "--format", "bestaudio/best", "--output"])
+            .arg(work.join(format!("{}.%(ext)s", stem)))
+            .args(["--", url])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(stderr);
+        if let Some(path) = &self.search_path {
+            command.env("PATH", path);
+        }
+        match command.spawn() {
+            Ok(child) => Ok(Download { key: url.to_string(), work, child }),
+            Err(e) => {
+                let _ = fs::remove_dir_all(&work);
+                Err(match e.kind() {
+                    io::ErrorKind::NotFound => String::from("yt-dlp is not installed"),
+                    _ => format!("cannot run yt-dlp: {}", e),
+                })
+            }
+        }
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.entries.values().filter(|entry| entry.file.is_some()).map(|entry| entry.size).sum()
+    }
+
+    /// Buang file yang paling lama tidak dipakai sampai total ukuran di bawah
+    /// `max_bytes`. Lagu yang di-pin dan `playing` (file-nya sedang dibaca
+    /// mpv) tidak ikut dihitung untuk dibuang.
+    pub fn evict(&mut self, max_bytes: u64, playing: Option<&str>) -> Vec<String> {
+        let mut total = self.total_size();
+        let mut candidates: Vec<(String, u64)> = self
+            .entries
+            .iter()
+            .filter(|(key, entry)| entry.file.is_some() && !entry.pinned && Some(key.as_str()) != playing)
+            .map(|(key, entry)| (key.clone(), entry.last_used))
+            .collect();
+        candidates.sort_by_key(|(_, last_used)| *last_used);
+
+        let mut evicted = Vec::new();
+        for (key, _) in candidates {
+            if total <= max_bytes {
+                break;
+            }
+            let Some(entry) = self.entries.remove(&key) else { continue };
+            if let Some(file) = &entry.file
+                && let Err(e) = fs::remove_file(self.dir.join(file))
+                && e.kind() != io::ErrorKind::NotFound
+            {
+                // Gagal hapus: biarkan tercatat supaya tidak jadi file yatim
+                self.entries.insert(key, entry);
+                continue;
+            }
+            total -= entry.size;
+            evicted.push(key);
+        }
+        evicted
+    }
+}
+
+/// Download yang belum selesai dihentikan (kill + wait) saat terminus keluar,
+/// beserta folder kerjanya
+impl Drop for AudioCache {
+    fn drop(&mut self) {
+        for mut download in self.running.drain(..) {
+            if let Err(e) = download.child.kill().and_then(|_| download.child.wait()) {
+                log::warn!("cannot stop yt-dlp for {}: {}", download.key, e);
+            }
+            let _ = fs::remove_dir_all(&download.work);
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Nama dasar file cache: hash dari kunci, supaya aman sebagai nama file
+fn file_stem(key: &str) -> String {
+    format!("{:x}", md5::compute(key.as_bytes()))
+}
+
+/// yt-dlp memilih ekstensinya sendiri, jadi file lengkapnya dicari ulang
+fn file_name(key: &str) -> String {
+    format!("{}.audio", file_stem(key))
+}
+
+/// Pindahkan hasil yt-dlp ke `<hash>.audio`. File yang belum selesai tidak
+/// pernah terlihat sebagai cache.
+fn finish(download: &Download, dir: &Path, status: ExitStatus) -> Result<u64, String> {
+    if !status.success() {
+        let stderr = fs::read_to_string(download.work.join("stderr.log")).unwrap_or_default();
+        return Err(stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| line.trim().to_string())
+            .unwrap_or_else(|| format!("yt-dlp exited with {}", status)));
+    }
+    let stem = file_stem(&download.key);
+    let target = dir.join(file_name(&download.key));
+    let entries = fs::read_dir(&download.work).map_err(|e| e.to_string())?;
+    let downloaded = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .find(|path| {
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            name.starts_with(&format!("{}.", stem)) && !name.ends_with(".part") && !name.ends_with(".ytdl")
+        })
+        .ok_or("yt-dlp finished but no audio file was written")?;
+    fs::rename(&downloaded, &target).map_err(|e| format!("cannot move download into the cache: {}", e))?;
+    fs::metadata(&target).map(|meta| meta.len()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        os::unix::fs::PermissionsExt,
+        sync::OnceLock,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    /// yt-dlp palsu: URL berisi `ok` menulis file audio, `fail` gagal dengan
+    /// pesan error, `slow` menunggu lama (dan mencatat PID-nya)
+    const STUB: &str = r#"#!/bin/sh
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --output) out="$2"; shift ;;
+        --) url="$2"; shift ;;
+    esac
+    shift
+done
+case "$url" in
+    *ok*) printf 'audio\n' > "${out%.*}.m4a" ;;
+    *fail*) echo "ERROR: Video unavailable" >&2; exit 1 ;;
+    *slow*) echo $$ > "$(dirname "$out")/../$(basename "$url").pid"; exec sleep 30 ;;
+esac
+"#;
+
+    /// Folder berisi stub `yt-dlp`, dibuat sekali untuk semua test supaya
+    /// tidak ada yang menjalankan file yang masih dibuka untuk ditulis
+    fn stub_dir() -> &'static Path {
+        static DIR: OnceLock<PathBuf> = OnceLock::new();
+        DIR.get_or_init(|| {
+            let dir = std::env::temp_dir().join(format!("terminus-stub-ytdlp-{}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let stub = dir.join("yt-dlp");
+            fs::write(&stub, STUB).unwrap();
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+            dir
+        })
+    }
+
+    fn stub_cache(name: &str) -> AudioCache {
+        let dir = std::env::temp_dir().join(format!("terminus-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let mut cache = AudioCache::open(dir);
+        let mut path = OsString::from(stub_dir());
+        path.push(":");
+        path.push(std::env::var_os("PATH").unwrap_or_default());
+        cache.search_path = Some(path);
+        cache
+    }
+
+    /// Folder kerja `.<hash>.download` yang masih tersisa
+    fn work_dirs(cache: &AudioCache) -> Vec<PathBuf> {
+        fs::read_dir(&cache.dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.to_string_lossy().ends_with(".download"))
+            .collect()
+    }
+
+    fn poll_until(cache: &mut AudioCache, count: usize) -> Vec<CacheEvent> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut events = Vec::new();
+        while events.len() < count && Instant::now() < deadline {
+            events.extend(cache.poll());
+            thread::sleep(Duration::from_millis(20));
+        }
+        events
+    }
+
+    #[test]
+    fn downloads_through_stub_ytdlp() {
+        let mut cache = stub_cache("stub");
+        assert!(cache.request("https://example.com/ok"));
+        assert!(cache.request("https://example.com/fail"));
+        assert!(!cache.request("https://example.com/ok"), "already queued");
+
+        let events = poll_until(&mut cache, 2);
+        let cached = cache.cached_path("https://example.com/ok").map(|path| fs::read_to_string(path).unwrap());
+        let leftovers = work_dirs(&cache);
+        fs::remove_dir_all(&cache.dir).unwrap();
+
+        assert_eq!(events.len(), 2, "{:?}", events);
+        for event in &events {
+            match event {
+                CacheEvent::Finished { key, size } => {
+                    assert_eq!(key, "https://example.com/ok");
+                    assert_eq!(*size, 6);
+                }
+                CacheEvent::Failed { key, error } => {
+                    assert_eq!(key, "https://example.com/fail");
+                    assert_eq!(error, "ERROR: Video unavailable");
+                }
+            }
+        }
+        assert_eq!(cached.as_deref(), Some("audio\n"));
+        assert!(!cache.is_downloading("https://example.com/ok"));
+        assert_eq!(leftovers, Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn running_downloads_are_capped_and_killed_on_drop() {
+        let mut cache = stub_cache("drop");
+        for name in ["slow-a", "slow-b", "slow-c"] {
+            cache.request(&format!("https://example.com/{}", name));
+        }
+        cache.poll();
+        assert_eq!(cache.running.len(), MAX_RUNNING);
+        assert_eq!(cache.queue.len(), 1);
+        assert!(cache.is_downloading("https://example.com/slow-c"));
+
+        // Tunggu sampai stub sempat mencatat PID-nya
+        let dir = cache.dir.clone();
+        let pid_file = |name: &str| dir.join(format!("{}.pid", name));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !(pid_file("slow-a").exists() && pid_file("slow-b").exists()) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        let pids: Vec<String> = ["slow-a", "slow-b"]
+            .iter()
+            .map(|name| fs::read_to_string(pid_file(name)).unwrap().trim().to_string())
+            .collect();
+        assert_eq!(work_dirs(&cache).len(), MAX_RUNNING);
+
+        drop(cache);
+        let leftovers: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.to_string_lossy().ends_with(".download"))
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(leftovers, Vec::<PathBuf>::new());
+        for pid in pids {
+            assert!(!Path::new("/proc").join(&pid).exists(), "yt-dlp {} still running", pid);
+        }
+    }
+
+    /// Cache di folder sementara dengan satu file per kunci; `last_used` naik
+    /// sesuai urutan, jadi kunci pertama yang paling lama
+    fn cache_with(name: &str, keys: &[&str]) -> AudioCache {
+        let dir = std::env::temp_dir().join(format!("terminus-cache-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut cache = AudioCache::open(dir.clone());
+        for (i, key) in keys.iter().enumerate() {
+            fs::write(dir.join(file_name(key)), [0u8; 10]).unwrap();
+            let entry = CacheEntry { file: Some(file_name(key)), size: 10, last_used: i as u64, pinned: false };
+            cache.entries.insert(key.to_string(), entry);
+        }
+        cache
+    }
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let mut cache = cache_with("lru", &["a", "b", "c"]);
+        let evicted = cache.evict(15, None);
+        let remaining = cache.total_size();
+        fs::remove_dir_all(&cache.dir).unwrap();
+
+        assert_eq!(evicted, ["a", "b"]);
+        assert_eq!(remaining, 10);
+    }
+
+    #[test]
+    fn keeps_pinned_and_playing_songs() {
+        let mut cache = cache_with("keep", &["a", "b", "c"]);
+        cache.set_pinned("b", true);
+        let evicted = cache.evict(0, Some("a"));
+        let kept = (cache.cached_path("a").is_some(), cache.cached_path("b").is_some());
+        fs::remove_dir_all(&cache.dir).unwrap();
+
+        assert_eq!(evicted, ["c"]);
+        assert_eq!(kept, (true, true));
+    }
+}
@@ -0,0 +1,227 @@
+// src/tools/control.rs
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+use serde_json::{json, Value};
+
+/// Batas waktu menunggu event loop menjawab satu perintah
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Socket per user: `$XDG_RUNTIME_DIR/terminus.sock`, atau di temp dir
+/// dengan nama user kalau runtime dir tidak ada
+pub fn socket_path() -> PathBuf {
+    match dirs::runtime_dir() {
+        Some(dir) => dir.join("terminus.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_else(|_| String::from("user"));
+            std::env::temp_dir().join(format!("terminus-{}.sock", user))
+        }
+    }
+}
+
+/// Perintah yang bisa dikirim lewat `terminus ctl`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    /// Tanpa argumen: lanjutkan / putar lagu terpilih
+    Play(Option<String>),
+    Pause,
+    Toggle,
+    Next,
+    Prev,
+    Volume(Option<VolumeChange>),
+    Enqueue(String),
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolumeChange {
+    Set(u8),
+    Delta(i16),
+}
+
+impl ControlCommand {
+    /// Parse satu baris seperti `volume +5` atau `enqueue ~/music/a.mp3`
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, arg) = match line.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, Some(arg.trim()).filter(|a| !a.is_empty())),
+            None => (line, None),
+        };
+        match (name.to_ascii_lowercase().as_str(), arg) {
+            ("play", arg) => Ok(ControlCommand::Play(arg.map(str::to_string))),
+            ("pause", None) => Ok(ControlCommand::Pause),
+            ("toggle", None) => Ok(ControlCommand::Toggle),
+            ("next", None) => Ok(ControlCommand::Next),
+            ("prev" | "previous", None) => Ok(ControlCommand::Prev),
+            ("volume", None) => Ok(ControlCommand::Volume(None)),
+            ("volume", Some(arg)) => {
+                let change = if arg.starts_with('+') || arg.starts_with('-') {
+                    arg.parse().map(VolumeChange::Delta)
+                } else {
+                    arg.parse().map(VolumeChange::Set)
+                };
+                change
+                    .map(|change| ControlCommand::Volume(Some(change)))
+                    .map_err(|_| format!("invalid volume `{}`", arg))
+            }
+            ("enqueue", Some(arg)) => Ok(ControlCommand::Enqueue(arg.to_string())),
+            ("enqueue", None) => Err(String::from("enqueue needs a song or path")),
+            ("status", None) => Ok(ControlCommand::Status),
+            ("", _) => Err(String::from("empty command")),
+            (name, Some(_)) if ["pause", "toggle", "next", "prev", "previous", "status"].contains(&name) => {
+                Err(format!("`{}` takes no arguments", name))
+            }
+            (name, _) => Err(format!("unknown command `{}`", name)),
+        }
+    }
+}
+
+/// Satu perintah yang menunggu dijalankan di event loop
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    reply: Sender<Value>,
+}
+
+impl ControlRequest {
+    pub fn respond(self, result: Result<Value, String>) {
+        let reply = match result {
+            Ok(Value::Object(mut data)) => {
+                data.insert(String::from("ok"), Value::Bool(true));
+                Value::Object(data)
+            }
+            Ok(_) => json!({ "ok": true }),
+            Err(error) => json!({ "ok": false, "error": error }),
+        };
+        let _ = self.reply.send(reply);
+    }
+}
+
+/// Listener Unix socket. Koneksi diterima di thread sendiri; perintahnya
+/// diteruskan ke `requests` dan dijalankan oleh `run_app`.
+pub struct ControlServer {
+    path: PathBuf,
+    pub requests: Receiver<ControlRequest>,
+}
+
+impl ControlServer {
+    pub fn bind() -> io::Result<Self> {
+        let path = socket_path();
+        if path.exists() {
+            // Socket masih hidup berarti ada instance lain; kalau tidak, sisa crash
+            if UnixStream::connect(&path).is_ok() {
+                return Err(io::Error::new(io::ErrorKind::AddrInUse, "another terminus is already running"));
+            }
+            fs::remove_file(&path)?;
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let listener = UnixListener::bind(&path)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let tx = tx.clone();
+                thread::spawn(move || handle_client(stream, &tx));
+            }
+        });
+        Ok(Self { path, requests: rx })
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn handle_client(stream: UnixStream, requests: &Sender<ControlRequest>) {
+    let _ = stream.set_read_timeout(Some(REPLY_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+
+    let reply = match ControlCommand::parse(&line) {
+        Ok(command) => {
+            let (reply_tx, reply_rx) = mpsc::channel();
+            if requests.send(ControlRequest { command, reply: reply_tx }).is_err() {
+                return;
+            }
+            reply_rx
+                .recv_timeout(REPLY_TIMEOUT)
+                .unwrap_or_else(|_| json!({ "ok": false, "error": "terminus did not respond" }))
+        }
+        Err(error) => json!({ "ok": false, "error": error }),
+    };
+    let _ = (&stream).write_all(format!("{}\n", reply).as_bytes());
+}
+
+/// Client untuk `terminus ctl`: kirim satu baris, kembalikan balasan JSON
+pub fn send(line: &str) -> io::Result<Value> {
+    let path = socket_path();
+    let stream = UnixStream::connect(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused => {
+            io::Error::new(e.kind(), format!("terminus is not running ({})", path.display()))
+        }
+        _ => e,
+    })?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT * 2))?;
+    (&stream).write_all(format!("{}\n", line.trim()).as_bytes())?;
+
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    serde_json::from_str(&reply).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simple_commands() {
+        assert_eq!(ControlCommand::parse("pause"), Ok(ControlCommand::Pause));
+        assert_eq!(ControlCommand::parse("  TOGGLE \n"), Ok(ControlCommand::Toggle));
+        assert_eq!(ControlCommand::parse("previous"), Ok(ControlCommand::Prev));
+        assert_eq!(ControlCommand::parse("status"), Ok(ControlCommand::Status));
+        assert_eq!(ControlCommand::parse("play"), Ok(ControlCommand::Play(None)));
+        assert_eq!(ControlCommand::parse("play  Some Song "), Ok(ControlCommand::Play(Some(String::from("Some Song")))));
+    }
+
+    #[test]
+    fn parses_volume_changes() {
+        assert_eq!(ControlCommand::parse("volume"), Ok(ControlCommand::Volume(None)));
+        assert_eq!(ControlCommand::parse("volume 40"), Ok(ControlCommand::Volume(Some(VolumeChange::Set(40)))));
+        assert_eq!(ControlCommand::parse("volume +5"), Ok(ControlCommand::Volume(Some(VolumeChange::Delta(5)))));
+        assert_eq!(ControlCommand::parse("volume -10"), Ok(ControlCommand::Volume(Some(VolumeChange::Delta(-10)))));
+        assert!(ControlCommand::parse("volume 300").is_err());
+        assert!(ControlCommand::parse("volume loud").is_err());
+    }
+
+    #[test]
+    fn enqueue_keeps_the_whole_argument() {
+        assert_eq!(
+            ControlCommand::parse("enqueue ~/music/a b.mp3"),
+            Ok(ControlCommand::Enqueue(String::from("~/music/a b.mp3")))
+        );
+        assert!(ControlCommand::parse("enqueue").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(ControlCommand::parse("   "), Err(String::from("empty command")));
+        assert_eq!(ControlCommand::parse("next 2"), Err(String::from("`next` takes no arguments")));
+        assert_eq!(ControlCommand::parse("rewind"), Err(String::from("unknown command `rewind`")));
+    }
+}
@@ -0,0 +1,238 @@
+// src/tools/dedupe.rs
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+use url::Url;
+
+use crate::app::{expand_tilde, Song};
+
+/// Query parameter yang cuma dipakai untuk tracking dan tidak mengubah isi
+const TRACKING_PARAMS: &[&str] = &[
+    "feature", "si", "pp", "fbclid", "gclid", "dclid", "igshid", "ref", "ref_src",
+    "mc_cid", "mc_eid", "yclid", "spm", "ab_channel",
+];
+
+/// Kunci pembanding untuk sebuah lagu. Dua lagu dianggap duplikat kalau
+/// kuncinya sama: path lokal yang sudah dinormalisasi, atau URL kanonik.
+pub fn song_key(song: &Song) -> String {
+    match &song.path {
+        Some(path) if !path.is_empty() => format!("file://{}", normalize_path(path).display()),
+        _ => canonical_url(&song.url),
+    }
+}
+
+/// Bentuk kanonik sebuah URL atau path.
+///
+/// - Link YouTube (`youtu.be`, `watch?v=`, `shorts/`, `embed/`, `music.`) jadi
+///   `https://www.youtube.com/watch?v=<id>`
+/// - Parameter tracking (`utm_*`, `feature`, `si`, ...) dan fragment dibuang
+/// - Input tanpa scheme atau `file://` diperlakukan sebagai path lokal
+pub fn canonical_url(input: &str) -> String {
+    let input = input.trim();
+    let url = match Url::parse(input) {
+        Ok(url) if url.scheme() == "file" => {
+            return match url.to_file_path() {
+                Ok(path) => format!("file://{}", normalize_path(&path.to_string_lossy()).display()),
+                Err(_) => input.to_string(),
+            };
+        }
+        Ok(url) => url,
+        Err(_) if input.is_empty() => return String::new(),
+        Err(_) => {
+            // Link YouTube yang di-paste tanpa scheme, mis. `youtu.be/<id>`
+            let with_scheme = Url::parse(&format!("https://{}", input)).ok();
+            if let Some(id) = with_scheme.as_ref().and_then(youtube_id) {
+                return format!("https://www.youtube.com/watch?v={}", id);
+            }
+            // Tidak ada scheme: anggap path lokal
+            return format!("file://{}", normalize_path(input).display());
+        }
+    };
+
+    if let Some(id) = youtube_id(&url) {
+        return format!("https://www.youtube.com/watch?v={}", id);
+    }
+
+    let mut url = url;
+    url.set_fragment(None);
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    url.to_string()
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key)
+}
+
+/// Ambil video ID dari berbagai bentuk link YouTube
+pub fn youtube_id(url: &Url) -> Option<String> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let host = host
+        .trim_start_matches("www.")
+        .trim_start_matches("m.")
+        .trim_start_matches("music.");
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+
+    let id = match host {
+        "youtu.be" => segments.next()?.to_string(),
+        "youtube.com" | "youtube-nocookie.com" => match segments.next()? {
+            "watch" => url
+                .query_pairs()
+                .find(|(key, _)| key == "v")
+                .map(|(_, value)| value.into_owned())?,
+            "shorts" | "embed" | "live" | "v" => segments.next()?.to_string(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let valid = id.len() == 11
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(id)
+}
+
+/// Expand tilde, jadikan absolut, dan rapikan `.`/`..`. Kalau file-nya ada,
+/// symlink juga di-resolve lewat `fs::canonicalize`.
+pub fn normalize_path(path: &str) -> PathBuf {
+    let expanded = expand_tilde(path);
+    if let Ok(real) = fs::canonicalize(&expanded) {
+        return real;
+    }
+    let absolute = if expanded.is_absolute() {
+        expanded
+    } else {
+        std::env::current_dir().unwrap_or_default().join(expanded)
+    };
+    lexical_normalize(&absolute)
+}
+
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Cari lagu lain di playlist yang kuncinya sama dengan `song`
+pub fn find_duplicate_of(playlist: &[Song], song: &Song, skip: Option<usize>) -> Option<usize> {
+    let key = song_key(song);
+    if key.is_empty() {
+        return None;
+    }
+    playlist
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != skip)
+        .find(|(_, other)| song_key(other) == key)
+        .map(|(i, _)| i)
+}
+
+/// Kelompokkan index lagu yang duplikat. Tiap grup berisi minimal dua index,
+/// urut naik; grup diurutkan berdasarkan kemunculan pertama.
+pub fn find_duplicate_groups(playlist: &[Song]) -> Vec<Vec<usize>> {
+    let mut by_key: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, song) in playlist.iter().enumerate() {
+        by_key.entry(song_key(song)).or_default().push(i);
+    }
+    let mut groups: Vec<Vec<usize>> = by_key
+        .into_iter()
+        .filter(|(key, group)| !key.is_empty() && group.len() > 1)
+        .map(|(_, group)| group)
+        .collect();
+    groups.sort_by_key(|group| group[0]);
+    groups
+}
+
+/// Gabungkan satu grup duplikat jadi satu lagu: lagu pertama dipertahankan,
+/// field kosong diisi dari duplikatnya, dan URL diganti bentuk kanonik.
+pub fn merge_group(playlist: &[Song], group: &[usize]) -> Song {
+    let mut merged = playlist[group[0]].clone();
+    for &i in &group[1..] {
+        let other = &playlist[i];
+        if merged.title.trim().is_empty() {
+            merged.title = other.title.clone();
+        }
+        if merged.artist.trim().is_empty() {
+            merged.artist = other.artist.clone();
+        }
+        if merged.path.is_none() {
+            merged.path = other.path.clone();
+        }
+        if merged.speed.is_none() {
+            merged.speed = other.speed;
+        }
+        if merged.duration.is_none() {
+            merged.duration = other.duration;
+        }
+    }
+    if merged.url.starts_with("http://") || merged.url.starts_with("https://") {
+        merged.url = canonical_url(&merged.url);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WATCH: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+    #[test]
+    fn youtube_links_share_one_form() {
+        for link in [
+            "https://youtu.be/dQw4w9WgXcQ?si=abc",
+            "https://m.youtube.com/watch?v=dQw4w9WgXcQ&feature=share",
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ&list=RD",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+            "https://www.youtube.com/embed/dQw4w9WgXcQ",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ",
+            "youtu.be/dQw4w9WgXcQ",
+            "  https://www.youtube.com/watch?t=42&v=dQw4w9WgXcQ#comments  ",
+        ] {
+            assert_eq!(canonical_url(link), WATCH, "{}", link);
+        }
+    }
+
+    #[test]
+    fn invalid_youtube_id_is_not_rewritten() {
+        assert_eq!(canonical_url("https://youtu.be/short"), "https://youtu.be/short");
+    }
+
+    #[test]
+    fn tracking_params_and_fragment_are_dropped() {
+        assert_eq!(
+            canonical_url("https://example.com/a.mp3?utm_source=x&id=7&fbclid=y#t=10"),
+            "https://example.com/a.mp3?id=7"
+        );
+        assert_eq!(canonical_url("https://example.com/a.mp3?utm_medium=x"), "https://example.com/a.mp3");
+    }
+
+    #[test]
+    fn other_urls_are_kept() {
+        assert_eq!(canonical_url("http://radio.example:8000/stream"), "http://radio.example:8000/stream");
+    }
+
+    #[test]
+    fn paths_become_normalized_file_urls() {
+        assert_eq!(canonical_url("/no-such-dir/music/../a.mp3"), "file:///no-such-dir/a.mp3");
+        assert_eq!(canonical_url("file:///no-such-dir/./b.mp3"), "file:///no-such-dir/b.mp3");
+        assert_eq!(canonical_url("   "), "");
+    }
+}
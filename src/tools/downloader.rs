@@ -0,0 +1,381 @@
+// src/tools/downloader.rs
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{Receiver, TryRecvError},
+    time::Instant,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::tools::mpv_output::{self, OutputLine, Stream};
+
+/// Format hasil download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Mp3,
+    Opus,
+    Video,
+}
+
+impl Preset {
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Mp3 => "audio mp3",
+            Preset::Opus => "audio opus",
+            Preset::Video => "best video",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Preset::Mp3 => Preset::Opus,
+            Preset::Opus => Preset::Video,
+            Preset::Video => Preset::Mp3,
+        }
+    }
+
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Preset::Mp3 => &["--extract-audio", "--audio-format", "mp3", "--audio-quality", "0"],
+            Preset::Opus => &["--extract-audio", "--audio-format", "opus"],
+            Preset::Video => &["--format", "bestvideo*+bestaudio/best"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Finished(PathBuf),
+    Failed(String),
+    Cancelled,
+}
+
+/// Progress terakhir dari baris `[download]` yt-dlp
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Progress {
+    pub percent: f64,
+    pub total: Option<String>,
+    pub speed: Option<String>,
+    pub eta: Option<String>,
+}
+
+pub struct Job {
+    pub id: u64,
+    pub url: String,
+    pub preset: Preset,
+    pub state: JobState,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub progress: Progress,
+    /// yt-dlp menjalankan beberapa tahap (video, audio, konversi)
+    pub stage: Option<String>,
+    pub started: Option<Instant>,
+    pub finished: Option<Instant>,
+    child: Option<Child>,
+    output: Option<Receiver<OutputLine>>,
+    file: Option<PathBuf>,
+    last_error: Option<String>,
+}
+
+impl Job {
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, JobState::Queued | JobState::Running)
+    }
+
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.url)
+    }
+}
+
+/// Perubahan state job yang perlu dilaporkan ke user
+pub enum Notice {
+    Finished { title: String },
+    Failed { title: String, error: String },
+}
+
+/// Antrean download yt-dlp dengan batas jumlah proses yang berjalan bersamaan
+pub struct Downloader {
+    pub jobs: Vec<Job>,
+    next_id: u64,
+}
+
+/// Penanda baris `--print` supaya bisa dibedakan dari output yt-dlp lain
+const TITLE_PREFIX: &str = "[terminus:title] ";
+const ARTIST_PREFIX: &str = "[terminus:artist] ";
+const FILE_PREFIX: &str = "[terminus:file] ";
+
+impl Downloader {
+    pub fn new() -> Self {
+        Self { jobs: Vec::new(), next_id: 1 }
+    }
+
+    pub fn add(&mut self, url: String, preset: Preset) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id,
+            url,
+            preset,
+            state: JobState::Queued,
+            title: None,
+            artist: None,
+            progress: Progress::default(),
+            stage: None,
+            started: None,
+            finished: None,
+            child: None,
+            output: None,
+            file: None,
+            last_error: None,
+        });
+        id
+    }
+
+    pub fn running(&self) -> usize {
+        self.jobs.iter().filter(|job| job.state == JobState::Running).count()
+    }
+
+    pub fn queued(&self) -> usize {
+        self.jobs.iter().filter(|job| job.state == JobState::Queued).count()
+    }
+
+    /// Hentikan job yang antre atau berjalan
+    pub fn cancel(&mut self, index: usize) -> bool {
+        let Some(job) = self.jobs.get_mut(index).filter(|job| job.is_active()) else { return false };
+        if let Some(mut child) = job.child.take()
+            && let Err(e) = child.kill().and_then(|_| child.wait())
+        {
+            log::warn!("cannot stop yt-dlp for {}: {}", job.url, e);
+        }
+        job.output = None;
+        job.state = JobState::Cancelled;
+        job.finished = Some(Instant::now());
+        true
+    }
+
+    /// Masukkan lagi job yang gagal atau dibatalkan ke antrean
+    pub fn retry(&mut self, index: usize) -> bool {
+        let Some(job) = self.jobs.get_mut(index) else { return false };
+        if !matches!(job.state, JobState::Failed(_) | JobState::Cancelled) {
+            return false;
+        }
+        job.state = JobState::Queued;
+        job.progress = Progress::default();
+        job.stage = None;
+        job.started = None;
+        job.finished = None;
+        job.last_error = None;
+        true
+    }
+
+    /// Hapus job yang sudah tidak berjalan dari daftar
+    pub fn remove(&mut self, index: usize) -> bool {
+        if self.jobs.get(index).is_none_or(|job| job.is_active()) {
+            return false;
+        }
+        self.jobs.remove(index);
+        true
+    }
+
+    /// Baca output job yang berjalan dan mulai job antrean sampai `limit`
+    pub fn poll(&mut self, dir: &Path, limit: usize) -> Vec<Notice> {
+        let mut notices = Vec::new();
+        for job in &mut self.jobs {
+            if job.state == JobState::Running
+                && let Some(notice) = poll_job(job)
+            {
+                notices.push(notice);
+            }
+        }
+
+        let mut running = self.running();
+        for job in &mut self.jobs {
+            if running >= limit.max(1) {
+                break;
+            }
+            if job.state != JobState::Queued {
+                continue;
+            }
+            match start(job, dir) {
+                Ok(()) => running += 1,
+                Err(e) => {
+                    let error = if e.kind() == io::ErrorKind::NotFound {
+                        String::from("yt-dlp is not installed")
+                    } else {
+                        format!("cannot run yt-dlp: {}", e)
+                    };
+                    job.state = JobState::Failed(error.clone());
+                    notices.push(Notice::Failed { title: job.display_title().to_string(), error });
+                }
+            }
+        }
+        notices
+    }
+}
+
+/// yt-dlp yang masih berjalan dihentikan (kill + wait) saat terminus keluar,
+/// supaya tidak terus mengunduh tanpa ada yang membaca hasilnya
+impl Drop for Downloader {
+    fn drop(&mut self) {
+        let active = (0..self.jobs.len()).filter(|index| self.cancel(*index)).count();
+        if active > 0 {
+            log::info!("cancelled {} active download(s) on exit", active);
+        }
+    }
+}
+
+fn start(job: &mut Job, dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut child = Command::new("yt-dlp")
+        .args(["--no-playlist", "--newline", "--progress", "--no-simulate"])
+        .args(["--print", &format!("before_dl:{}%(title)s", TITLE_PREFIX)])
+        .args(["--print", &format!("before_dl:{}%(artist,uploader|)s", ARTIST_PREFIX)])
+        .args(["--print", &format!("after_move:{}%(filepath)s", FILE_PREFIX)])
+        .args(job.preset.args())
+        .arg("--paths")
+        .arg(dir)
+        .args(["--output", "%(title)s [%(id)s].%(ext)s", "--", &job.url])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    log::info!("download {} started: {} ({})", job.id, job.url, job.preset.label());
+    job.output = Some(mpv_output::capture(&mut child));
+    job.child = Some(child);
+    job.state = JobState::Running;
+    job.started = Some(Instant::now());
+    job.file = None;
+    Ok(())
+}
+
+fn poll_job(job: &mut Job) -> Option<Notice> {
+    let output = job.output.as_ref()?;
+    let mut lines = Vec::new();
+    let ended = loop {
+        match output.try_recv() {
+            Ok(line) => lines.push(line),
+            Err(TryRecvError::Empty) => break false,
+            Err(TryRecvError::Disconnected) => break true,
+        }
+    };
+    for line in lines {
+        handle_line(job, line);
+    }
+    if !ended {
+        return None;
+    }
+
+    // Kedua stream sudah EOF; proses tinggal diambil exit code-nya
+    job.output = None;
+    job.finished = Some(Instant::now());
+    let status = job.child.take().map(|mut child| child.wait());
+    let title = job.display_title().to_string();
+    match (status, job.file.clone()) {
+        (Some(Ok(status)), Some(file)) if status.success() => {
+            log::info!("download {} finished: {}", job.id, file.display());
+            job.progress.percent = 100.0;
+            job.state = JobState::Finished(file);
+            Some(Notice::Finished { title })
+        }
+        (Some(Ok(status)), _) => {
+            let error = job.last_error.clone().unwrap_or_else(|| {
+                if status.success() {
+                    String::from("yt-dlp did not report the downloaded file")
+                } else {
+                    format!("yt-dlp exited with {}", status)
+                }
+            });
+            log::warn!("download {} failed: {}", job.id, error);
+            job.state = JobState::Failed(error.clone());
+            Some(Notice::Failed { title, error })
+        }
+        (Some(Err(e)), _) => {
+            job.state = JobState::Failed(e.to_string());
+            Some(Notice::Failed { title, error: e.to_string() })
+        }
+        // Dibatalkan di tengah jalan
+        (None, _) => None,
+    }
+}
+
+fn handle_line(job: &mut Job, line: OutputLine) {
+    let text = line.text.trim();
+    if let Some(title) = text.strip_prefix(TITLE_PREFIX) {
+        job.title = Some(title.to_string()).filter(|title| !title.is_empty());
+    } else if let Some(artist) = text.strip_prefix(ARTIST_PREFIX) {
+        job.artist = Some(artist.to_string()).filter(|artist| !artist.is_empty() && artist != "NA");
+    } else if let Some(file) = text.strip_prefix(FILE_PREFIX) {
+        job.file = Some(PathBuf::from(file));
+    } else if let Some(progress) = parse_progress(text) {
+        job.progress = progress;
+    } else if let Some(stage) = text.strip_prefix("[ExtractAudio] ").or_else(|| text.strip_prefix("[Merger] ")) {
+        job.stage = Some(stage.to_string());
+    } else if line.stream == Stream::Stderr && text.starts_with("ERROR:") {
+        job.last_error = Some(text.trim_start_matches("ERROR:").trim().to_string());
+    }
+}
+
+/// Parse baris progress yt-dlp, mis.
+/// `[download]  42.3% of ~  3.45MiB at  1.20MiB/s ETA 00:03 (frag 2/10)`
+/// atau `[download] 100% of    3.45MiB in 00:00:02 at 1.51MiB/s`
+pub fn parse_progress(line: &str) -> Option<Progress> {
+    let rest = line.strip_prefix("[download]")?.trim_start();
+    let (percent, rest) = rest.split_once('%')?;
+    let percent: f64 = percent.trim().parse().ok()?;
+
+    let tokens: Vec<&str> = rest.split_whitespace().collect();
+    let after = |word: &str| {
+        let index = tokens.iter().position(|token| *token == word)?;
+        tokens[index + 1..]
+            .iter()
+            .find(|token| **token != "~")
+            .map(|token| token.to_string())
+            .filter(|value| value != "Unknown")
+    };
+    Some(Progress { percent, total: after("of"), speed: after("at"), eta: after("ETA") })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> Option<String> {
+        Some(value.to_string())
+    }
+
+    #[test]
+    fn parses_running_download() {
+        let progress = parse_progress("[download]  42.3% of ~  3.45MiB at  1.20MiB/s ETA 00:03 (frag 2/10)");
+        assert_eq!(
+            progress,
+            Some(Progress { percent: 42.3, total: text("3.45MiB"), speed: text("1.20MiB/s"), eta: text("00:03") })
+        );
+    }
+
+    #[test]
+    fn parses_finished_download() {
+        let progress = parse_progress("[download] 100% of    3.45MiB in 00:00:02 at 1.51MiB/s");
+        assert_eq!(
+            progress,
+            Some(Progress { percent: 100.0, total: text("3.45MiB"), speed: text("1.51MiB/s"), eta: None })
+        );
+    }
+
+    #[test]
+    fn unknown_values_are_dropped() {
+        let progress = parse_progress("[download]   0.0% of Unknown at Unknown B/s ETA Unknown").unwrap();
+        assert_eq!(progress, Progress { percent: 0.0, total: None, speed: None, eta: None });
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(parse_progress("[download] Destination: song.webm"), None);
+        assert_eq!(parse_progress("[ExtractAudio] Destination: song.mp3"), None);
+        assert_eq!(parse_progress("[youtube] dQw4w9WgXcQ: 50% done"), None);
+    }
+}
@@ -0,0 +1,52 @@
+// src/tools/file_watch.rs
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+use inotify::{Inotify, WatchMask};
+
+/// Pantau satu file lewat inotify. Yang dipantau folder induknya, karena
+/// editor dan tool sync biasanya menulis ke file sementara lalu `rename`,
+/// sehingga watch pada file lamanya ikut hilang.
+pub struct FileWatcher {
+    events: Receiver<()>,
+}
+
+impl FileWatcher {
+    pub fn start(path: &Path) -> io::Result<Self> {
+        let dir = path.parent().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no parent"))?;
+        let name: OsString = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+            .to_os_string();
+        fs::create_dir_all(dir)?;
+
+        let mut inotify = Inotify::init()?;
+        inotify.watches().add(dir, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO)?;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(events) = inotify.read_events_blocking(&mut buffer) {
+                let touched = events.into_iter().any(|event| event.name == Some(name.as_os_str()));
+                // Receiver sudah di-drop berarti aplikasi sudah keluar
+                if touched && tx.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { events: rx })
+    }
+
+    /// `true` kalau file berubah sejak pemanggilan terakhir
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while self.events.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
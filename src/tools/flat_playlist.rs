@@ -0,0 +1,189 @@
+// src/tools/flat_playlist.rs
+use std::{
+    fs,
+    io::{self, IsTerminal, Read},
+    path::Path,
+};
+use serde_json::Value;
+
+use crate::app::Song;
+use crate::tools::dedupe;
+
+/// Satu entri dump yt-dlp
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub song: Song,
+    /// `[Private video]`/`[Deleted video]` tetap ditampilkan, tapi tidak bisa diputar
+    pub available: bool,
+}
+
+/// Playlist dari `yt-dlp --flat-playlist -J <url>`
+#[derive(Debug, Clone)]
+pub struct FlatPlaylist {
+    pub title: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+/// Baca dump dari file, atau dari stdin kalau path-nya `-`
+pub fn read(path: &Path) -> Result<FlatPlaylist, String> {
+    let text = if path == Path::new("-") {
+        let mut stdin = io::stdin();
+        if stdin.is_terminal() {
+            return Err(String::from("stdin is a terminal; pipe yt-dlp output into terminus"));
+        }
+        let mut text = String::new();
+        stdin.read_to_string(&mut text).map_err(|e| format!("cannot read stdin: {}", e))?;
+        text
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+    };
+    parse(&text)
+}
+
+/// Terima output `-J` (satu objek JSON) maupun `-j` (satu objek per baris)
+pub fn parse(text: &str) -> Result<FlatPlaylist, String> {
+    let playlist = match serde_json::from_str::<Value>(text) {
+        Ok(root) => {
+            let mut entries = Vec::new();
+            collect(&root, None, &mut entries);
+            FlatPlaylist { title: text_field(&root, &["title"]), entries }
+        }
+        Err(e) => {
+            let mut entries = Vec::new();
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                let value: Value = serde_json::from_str(line).map_err(|_| format!("not yt-dlp JSON output: {}", e))?;
+                collect(&value, None, &mut entries);
+            }
+            FlatPlaylist { title: None, entries }
+        }
+    };
+    if playlist.entries.is_empty() {
+        return Err(String::from("no videos found in the yt-dlp output"));
+    }
+    Ok(playlist)
+}
+
+/// Playlist bisa bersarang (mis. tab channel); channel induk dipakai kalau
+/// entrinya tidak punya channel sendiri
+fn collect(value: &Value, channel: Option<&str>, entries: &mut Vec<Entry>) {
+    let own = text_field(value, &["channel", "uploader"]);
+    let channel = own.as_deref().or(channel);
+    match value.get("entries").and_then(Value::as_array) {
+        Some(children) => {
+            for child in children.iter().filter(|child| child.is_object()) {
+                collect(child, channel, entries);
+            }
+        }
+        None => entries.extend(entry(value, channel)),
+    }
+}
+
+fn entry(value: &Value, channel: Option<&str>) -> Option<Entry> {
+    let url = text_field(value, &["webpage_url", "url"])
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .or_else(|| {
+            // yt-dlp lama hanya menulis ID untuk entri YouTube
+            let id = text_field(value, &["id"])?;
+            (text_field(value, &["ie_key"]).as_deref() == Some("Youtube"))
+                .then(|| format!("https://www.youtube.com/watch?v={}", id))
+        })?;
+    let url = dedupe::canonical_url(&url);
+    let title = text_field(value, &["title"]).unwrap_or_else(|| url.clone());
+    let available = !matches!(title.as_str(), "[Private video]" | "[Deleted video]")
+        && text_field(value, &["availability"]).as_deref() != Some("private");
+    Some(Entry {
+        song: Song {
+            title,
+            artist: channel.unwrap_or_default().to_string(),
+            url,
+            path: None,
+            radio: false,
+            speed: None,
+            duration: value.get("duration").and_then(Value::as_f64).map(|secs| secs.round() as u64),
+        },
+        available,
+    })
+}
+
+fn text_field(value: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| value.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(playlist: &FlatPlaylist) -> Vec<&str> {
+        playlist.entries.iter().map(|entry| entry.song.url.as_str()).collect()
+    }
+
+    #[test]
+    fn single_object_dump_with_entries() {
+        let playlist = parse(
+            r#"{"_type": "playlist", "title": "Mix", "channel": "Owner", "entries": [
+                {"url": "https://www.youtube.com/watch?v=aaaaaaaaaaa", "title": "First", "channel": "Band", "duration": 61.4},
+                {"url": "https://www.youtube.com/watch?v=bbbbbbbbbbb", "title": "[Private video]", "duration": null}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(playlist.title.as_deref(), Some("Mix"));
+        assert_eq!(
+            urls(&playlist),
+            ["https://www.youtube.com/watch?v=aaaaaaaaaaa", "https://www.youtube.com/watch?v=bbbbbbbbbbb"]
+        );
+        let first = &playlist.entries[0];
+        assert_eq!((first.song.title.as_str(), first.song.artist.as_str()), ("First", "Band"));
+        assert_eq!(first.song.duration, Some(61));
+        assert!(first.available);
+        // Channel playlist dipakai kalau entri tidak punya channel sendiri
+        assert_eq!(playlist.entries[1].song.artist, "Owner");
+        assert!(!playlist.entries[1].available);
+    }
+
+    #[test]
+    fn one_object_per_line_dump() {
+        let playlist = parse(
+            "{\"webpage_url\": \"https://example.com/a\", \"title\": \"A\", \"uploader\": \"X\"}\n\n\
+             {\"webpage_url\": \"https://example.com/b\", \"title\": \"B\", \"availability\": \"private\"}\n",
+        )
+        .unwrap();
+
+        assert_eq!(playlist.title, None);
+        assert_eq!(urls(&playlist), ["https://example.com/a", "https://example.com/b"]);
+        assert_eq!(playlist.entries[0].song.artist, "X");
+        assert!(playlist.entries[0].available);
+        assert!(!playlist.entries[1].available);
+    }
+
+    #[test]
+    fn entries_without_title_or_url() {
+        let playlist = parse(
+            r#"{"entries": [
+                {"url": "https://example.com/untitled"},
+                {"title": "No URL"},
+                {"id": "ccccccccccc", "ie_key": "Youtube", "title": "Old dump"},
+                {"id": "ddd", "ie_key": "Vimeo", "title": "Unknown site"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            urls(&playlist),
+            ["https://example.com/untitled", "https://www.youtube.com/watch?v=ccccccccccc"]
+        );
+        // Tanpa judul, URL dipakai sebagai judul
+        assert_eq!(playlist.entries[0].song.title, "https://example.com/untitled");
+    }
+
+    #[test]
+    fn empty_or_invalid_input_is_rejected() {
+        assert_eq!(parse("").unwrap_err(), "no videos found in the yt-dlp output");
+        assert_eq!(parse(r#"{"title": "Empty", "entries": []}"#).unwrap_err(), "no videos found in the yt-dlp output");
+        assert!(parse("not json\n").unwrap_err().starts_with("not yt-dlp JSON output"));
+    }
+}
@@ -0,0 +1,241 @@
+// src/tools/metadata.rs
+use std::{
+    io,
+    path::Path,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+use serde_json::Value;
+
+use crate::tools::source::SongSource;
+
+/// Info lagu yang bisa dipakai untuk mengisi form otomatis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    /// Durasi dalam detik
+    pub duration: Option<u64>,
+}
+
+/// Pencarian metadata untuk satu isi field URL, berjalan di thread sendiri
+pub struct Lookup {
+    /// Input yang sedang dicari; hasilnya dibuang kalau field sudah berubah
+    pub input: String,
+    pub started: Instant,
+    rx: Receiver<Result<Metadata, String>>,
+}
+
+impl Lookup {
+    /// Mulai lookup kalau inputnya sumber yang punya metadata: file lokal
+    /// (tag lewat ffprobe) atau URL yang dikenal yt-dlp. Radio dilewati.
+    pub fn start(input: &str) -> Option<Self> {
+        let source = SongSource::parse(input).ok()?;
+        let (tx, rx) = mpsc::channel();
+        match source {
+            SongSource::LocalFile(path) => {
+                thread::spawn(move || {
+                    let _ = tx.send(from_file(&path));
+                });
+            }
+            SongSource::HttpUrl(url) | SongSource::ResolvablePage(url) => {
+                let url = url.to_string();
+                thread::spawn(move || {
+                    let _ = tx.send(from_ytdlp(&url));
+                });
+            }
+            SongSource::Stream(_) => return None,
+        }
+        Some(Self { input: input.to_string(), started: Instant::now(), rx })
+    }
+
+    /// `None` selama masih berjalan
+    pub fn result(&self) -> Option<Result<Metadata, String>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(String::from("metadata lookup stopped unexpectedly"))),
+        }
+    }
+}
+
+fn run(command: &mut Command, name: &str) -> Result<Vec<u8>, String> {
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => format!("{} is not installed", name),
+            _ => format!("cannot run {}: {}", name, e),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or_default();
+        let last = last.trim().trim_start_matches("ERROR:").trim();
+        return Err(if last.is_empty() { format!("{} exited with {}", name, output.status) } else { last.to_string() });
+    }
+    Ok(output.stdout)
+}
+
+fn from_ytdlp(url: &str) -> Result<Metadata, String> {
+    let stdout = run(
+        Command::new("yt-dlp").args(["--dump-json", "--no-playlist", "--no-warnings", "--", url]),
+        "yt-dlp",
+    )?;
+    let info: Value = serde_json::from_slice(&stdout).map_err(|e| format!("unexpected yt-dlp output: {}", e))?;
+    Ok(parse_ytdlp(&info))
+}
+
+/// Field `--dump-json` yang dipakai: `track`/`title`, lalu
+/// `artist`/`creator`/`uploader`/`channel`, dan `duration`
+pub fn parse_ytdlp(info: &Value) -> Metadata {
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .filter_map(|key| info.get(*key).and_then(Value::as_str))
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    Metadata {
+        title: text(&["track", "title"]),
+        artist: text(&["artist", "creator", "uploader", "channel"]),
+        duration: info.get("duration").and_then(Value::as_f64).map(|secs| secs.round() as u64),
+    }
+}
+
+/// Tag file lokal lewat ffprobe; kalau tidak ada tag, coba pola nama file
+/// `Artist - Title.ext`
+fn from_file(path: &Path) -> Result<Metadata, String> {
+    let from_name = parse_file_name(path);
+    let stdout = match run(
+        Command::new("ffprobe").args(["-v", "quiet", "-print_format", "json", "-show_format", "-i"]).arg(path),
+        "ffprobe",
+    ) {
+        Ok(stdout) => stdout,
+        // Tanpa ffprobe nama file masih lebih baik daripada kosong
+        Err(e) if from_name.title.is_some() => {
+            log::debug!("ffprobe failed for {}: {}", path.display(), e);
+            return Ok(from_name);
+        }
+        Err(e) => return Err(e),
+    };
+    let info: Value = serde_json::from_slice(&stdout).map_err(|e| format!("unexpected ffprobe output: {}", e))?;
+    let tags = parse_ffprobe(&info);
+    Ok(Metadata {
+        title: tags.title.or(from_name.title),
+        artist: tags.artist.or(from_name.artist),
+        duration: tags.duration,
+    })
+}
+
+/// `format.tags` dari ffprobe; nama tag beda-beda huruf besarnya per format
+pub fn parse_ffprobe(info: &Value) -> Metadata {
+    let format = info.get("format");
+    let tag = |name: &str| {
+        format?
+            .get("tags")?
+            .as_object()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    Metadata {
+        title: tag("title"),
+        artist: tag("artist").or_else(|| tag("album_artist")),
+        duration: format
+            .and_then(|format| format.get("duration"))
+            .and_then(Value::as_str)
+            .and_then(|secs| secs.parse::<f64>().ok())
+            .map(|secs| secs.round() as u64),
+    }
+}
+
+fn parse_file_name(path: &Path) -> Metadata {
+    let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()) else {
+        return Metadata::default();
+    };
+    match stem.split_once(" - ") {
+        Some((artist, title)) => Metadata {
+            title: Some(title.trim().to_string()),
+            artist: Some(artist.trim().to_string()),
+            duration: None,
+        },
+        None => Metadata { title: Some(stem), ..Metadata::default() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn ytdlp_prefers_track_and_artist_fields() {
+        let info = fixture(
+            r#"{"id": "abc", "title": "Artist - Song (Official Video)", "track": "Song",
+                "artist": "Artist", "uploader": "ArtistVEVO", "duration": 215}"#,
+        );
+        assert_eq!(
+            parse_ytdlp(&info),
+            Metadata { title: Some("Song".into()), artist: Some("Artist".into()), duration: Some(215) }
+        );
+    }
+
+    #[test]
+    fn ytdlp_falls_back_to_uploader_and_rounds_duration() {
+        let info = fixture(r#"{"title": " Live set ", "artist": "", "uploader": "Some Channel", "duration": 3599.6}"#);
+        assert_eq!(
+            parse_ytdlp(&info),
+            Metadata { title: Some("Live set".into()), artist: Some("Some Channel".into()), duration: Some(3600) }
+        );
+
+        let info = fixture(r#"{"title": "Untitled", "channel": "Fallback", "duration": null}"#);
+        assert_eq!(parse_ytdlp(&info).artist.as_deref(), Some("Fallback"));
+        assert_eq!(parse_ytdlp(&info).duration, None);
+    }
+
+    #[test]
+    fn ffprobe_reads_tags_case_insensitively() {
+        let info = fixture(
+            r#"{"format": {"filename": "a.ogg", "duration": "183.480000",
+                "tags": {"TITLE": "Song", "ARTIST": "Artist", "ALBUM": "Album"}}}"#,
+        );
+        assert_eq!(
+            parse_ffprobe(&info),
+            Metadata { title: Some("Song".into()), artist: Some("Artist".into()), duration: Some(183) }
+        );
+    }
+
+    #[test]
+    fn ffprobe_falls_back_to_album_artist() {
+        let info = fixture(r#"{"format": {"duration": "59.5", "tags": {"title": "Song", "album_artist": "Band"}}}"#);
+        assert_eq!(
+            parse_ffprobe(&info),
+            Metadata { title: Some("Song".into()), artist: Some("Band".into()), duration: Some(60) }
+        );
+
+        // File tanpa tag sama sekali
+        assert_eq!(parse_ffprobe(&fixture(r#"{"format": {"duration": "N/A"}}"#)), Metadata::default());
+    }
+
+    #[test]
+    fn file_name_is_split_into_artist_and_title() {
+        assert_eq!(
+            parse_file_name(Path::new("/music/Some Artist - A Song.flac")),
+            Metadata { title: Some("A Song".into()), artist: Some("Some Artist".into()), duration: None }
+        );
+        assert_eq!(
+            parse_file_name(Path::new("/music/recording.wav")),
+            Metadata { title: Some("recording".into()), ..Metadata::default() }
+        );
+        assert_eq!(parse_file_name(Path::new("/")), Metadata::default());
+    }
+}
@@ -2,3 +2,21 @@
 pub mod music;
 pub mod video;
 pub mod file_tools;
+pub mod dedupe;
+pub mod date;
+pub mod source;
+pub mod radio;
+pub mod mpv_ipc;
+pub mod mpv_output;
+pub mod podcast;
+pub mod mpris;
+pub mod notify;
+pub mod scrobble;
+pub mod control;
+pub mod file_watch;
+pub mod audio_device;
+pub mod cache;
+pub mod downloader;
+pub mod metadata;
+pub mod flat_playlist;
+pub mod album_art;
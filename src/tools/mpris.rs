@@ -0,0 +1,486 @@
+// src/tools/mpris.rs
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use zbus::{
+    blocking::Connection,
+    fdo, interface,
+    zvariant::{ObjectPath, OwnedValue, Value},
+};
+
+use crate::app::{MAX_SPEED, MIN_SPEED};
+use crate::tools::mpv_ipc;
+
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.terminus";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+/// Seberapa sering durasi lagu ditanyakan ke mpv selama belum diketahui
+const DURATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Radio dan stream lain tidak pernah punya durasi; berhenti bertanya setelah ini
+const DURATION_POLL_LIMIT: u32 = 30;
+/// Seberapa sering posisi playback dibaca dari mpv untuk properti `Position`
+const POSITION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Perintah dari client MPRIS (media key, `playerctl`, ...) untuk event loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum MprisCommand {
+    PlayPause,
+    Play,
+    Pause,
+    Stop,
+    Next,
+    Previous,
+    /// Geser relatif, dalam mikrodetik
+    Seek(i64),
+    /// Pindah ke posisi absolut, dalam mikrodetik
+    SetPosition(i64),
+    /// 0.0 - 1.0
+    SetVolume(f64),
+    SetRate(f64),
+    OpenUri(String),
+}
+
+/// Keadaan player yang diekspos lewat D-Bus
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MprisState {
+    pub status: PlaybackStatus,
+    pub title: String,
+    pub artist: String,
+    pub url: String,
+    /// Durasi lagu dalam mikrodetik, kalau mpv sudah tahu
+    pub length: Option<i64>,
+    /// 0.0 - 1.0
+    pub volume: f64,
+    /// Kecepatan putar, `MIN_SPEED` - `MAX_SPEED`
+    pub rate: f64,
+    /// Naik setiap kali lagu berganti, dipakai untuk `mpris:trackid`
+    pub track: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    #[default]
+    Stopped,
+}
+
+impl PlaybackStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PlaybackStatus::Playing => "Playing",
+            PlaybackStatus::Paused => "Paused",
+            PlaybackStatus::Stopped => "Stopped",
+        }
+    }
+}
+
+impl MprisState {
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        let mut metadata = HashMap::new();
+        if self.status == PlaybackStatus::Stopped {
+            metadata.insert("mpris:trackid".to_string(), owned(ObjectPath::from_static_str_unchecked(NO_TRACK)));
+            return metadata;
+        }
+
+        let track_id = format!("/org/terminus/track/{}", self.track);
+        if let Ok(path) = ObjectPath::try_from(track_id) {
+            metadata.insert("mpris:trackid".to_string(), owned(path));
+        }
+        metadata.insert("xesam:title".to_string(), owned(self.title.as_str()));
+        if !self.artist.is_empty() {
+            metadata.insert("xesam:artist".to_string(), owned(vec![self.artist.as_str()]));
+        }
+        if !self.url.is_empty() {
+            metadata.insert("xesam:url".to_string(), owned(self.url.as_str()));
+        }
+        if let Some(length) = self.length {
+            metadata.insert("mpris:length".to_string(), owned(length));
+        }
+        metadata
+    }
+}
+
+fn owned<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+    // Hanya gagal untuk file descriptor, yang tidak pernah dipakai di sini
+    value.into().try_to_owned().expect("metadata value without file descriptors")
+}
+
+/// Service MPRIS2 di session bus. Method call dijawab di thread internal zbus
+/// dan diteruskan ke event loop lewat `commands`.
+pub struct MprisServer {
+    connection: Connection,
+    state: Arc<Mutex<MprisState>>,
+    pub commands: Receiver<MprisCommand>,
+    duration_poll: DurationPoll,
+    /// Membangunkan `PositionPoller` setelah seek; thread-nya berhenti saat ini di-drop
+    seeks: Sender<()>,
+}
+
+/// Pertanyaan durasi ke mpv untuk satu track. IPC bisa menunggu sampai
+/// timeout, jadi dijalankan di thread sendiri, satu per satu.
+struct DurationPoll {
+    track: u64,
+    attempts: u32,
+    last: Instant,
+    pending: Option<Receiver<Option<i64>>>,
+}
+
+impl DurationPoll {
+    /// Hasil pertanyaan yang sudah selesai, kalau ada
+    fn result(&mut self) -> Option<i64> {
+        let rx = self.pending.as_ref()?;
+        match rx.try_recv() {
+            Ok(length) => {
+                self.pending = None;
+                length
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                None
+            }
+        }
+    }
+
+    fn start(&mut self, track: u64) {
+        if track != self.track {
+            // Jawaban untuk track lama tidak berlaku lagi
+            *self = DurationPoll { track, attempts: 0, last: Instant::now(), pending: None };
+        }
+        if self.pending.is_some()
+            || self.attempts >= DURATION_POLL_LIMIT
+            || self.last.elapsed() < DURATION_POLL_INTERVAL
+        {
+            return;
+        }
+        self.attempts += 1;
+        self.last = Instant::now();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let length = mpv_ipc::get_property("duration")
+                .ok()
+                .and_then(|value| value.as_f64())
+                .map(|secs| (secs * 1_000_000.0) as i64);
+            let _ = tx.send(length);
+        });
+        self.pending = Some(rx);
+    }
+}
+
+/// Membaca `time-pos` dari mpv di thread sendiri, supaya event loop maupun
+/// thread zbus tidak pernah menunggu IPC. Hasilnya dipakai oleh properti
+/// `Position` dan sinyal `Seeked`.
+struct PositionPoller {
+    connection: Connection,
+    state: Arc<Mutex<MprisState>>,
+    position: Arc<AtomicI64>,
+    seeks: Receiver<()>,
+}
+
+impl PositionPoller {
+    fn run(self) {
+        loop {
+            let seeked = match self.seeks.recv_timeout(POSITION_POLL_INTERVAL) {
+                Ok(()) => true,
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+            let stopped = self.state.lock().map(|state| state.status == PlaybackStatus::Stopped).unwrap_or(true);
+            let position = if stopped { 0 } else { current_position() };
+            self.position.store(position, Ordering::Relaxed);
+            if seeked {
+                let _ = self.connection.emit_signal(None::<&str>, OBJECT_PATH, PLAYER_INTERFACE, "Seeked", &position);
+            }
+        }
+    }
+}
+
+impl MprisServer {
+    pub fn start() -> zbus::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let state = Arc::new(Mutex::new(MprisState { volume: 1.0, rate: 1.0, ..Default::default() }));
+        let position = Arc::new(AtomicI64::new(0));
+
+        let connection = zbus::blocking::connection::Builder::session()?
+            .serve_at(OBJECT_PATH, Root)?
+            .serve_at(
+                OBJECT_PATH,
+                Player { state: Arc::clone(&state), position: Arc::clone(&position), commands: tx },
+            )?
+            .build()?;
+
+        // Instance kedua memakai nama unik sesuai konvensi MPRIS
+        if connection.request_name(BUS_NAME).is_err() {
+            connection.request_name(format!("{}.instance{}", BUS_NAME, std::process::id()))?;
+        }
+
+        let (seeks, seek_rx) = mpsc::channel();
+        let poller = PositionPoller { connection: connection.clone(), state: Arc::clone(&state), position, seeks: seek_rx };
+        thread::spawn(move || poller.run());
+
+        let duration_poll = DurationPoll { track: 0, attempts: 0, last: Instant::now(), pending: None };
+        Ok(Self { connection, state, commands: rx, duration_poll, seeks })
+    }
+
+    /// Sinkronkan keadaan player dan kirim `PropertiesChanged` untuk yang berubah
+    pub fn update(&mut self, mut next: MprisState) {
+        let previous = self.state.lock().map(|state| state.clone()).unwrap_or_default();
+
+        // Tidak semua `Song` tahu durasinya, jadi tanyakan ke mpv sampai diketahui
+        if next.track == previous.track {
+            next.length = next.length.or(previous.length);
+        }
+        if next.length.is_none() && next.status != PlaybackStatus::Stopped {
+            if self.duration_poll.track == next.track {
+                next.length = self.duration_poll.result();
+            }
+            if next.length.is_none() {
+                self.duration_poll.start(next.track);
+            }
+        }
+
+        if next == previous {
+            return;
+        }
+
+        let mut changed: HashMap<&str, Value> = HashMap::new();
+        if next.status != previous.status {
+            changed.insert("PlaybackStatus", Value::from(next.status.as_str()));
+        }
+        if next.volume != previous.volume {
+            changed.insert("Volume", Value::from(next.volume));
+        }
+        if next.rate != previous.rate {
+            changed.insert("Rate", Value::from(next.rate));
+        }
+        let metadata = next.metadata();
+        if metadata != previous.metadata() {
+            changed.insert("Metadata", Value::from(metadata));
+        }
+
+        if let Ok(mut state) = self.state.lock() {
+            *state = next;
+        }
+        if !changed.is_empty() {
+            let _ = self.connection.emit_signal(
+                None::<&str>,
+                OBJECT_PATH,
+                "org.freedesktop.DBus.Properties",
+                "PropertiesChanged",
+                &(PLAYER_INTERFACE, changed, Vec::<&str>::new()),
+            );
+        }
+    }
+
+    /// Beri tahu client bahwa posisi melompat (setelah Seek/SetPosition).
+    /// Sinyalnya dikirim oleh `PositionPoller` begitu posisi baru terbaca.
+    pub fn seeked(&self) {
+        let _ = self.seeks.send(());
+    }
+}
+
+/// Posisi playback mpv dalam mikrodetik
+fn current_position() -> i64 {
+    mpv_ipc::get_property("time-pos")
+        .ok()
+        .and_then(|value| value.as_f64())
+        .map(|secs| (secs * 1_000_000.0) as i64)
+        .unwrap_or(0)
+}
+
+struct Root;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> &str {
+        "terminus"
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<&str> {
+        vec!["file", "http", "https"]
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<&str> {
+        vec!["audio/mpeg", "audio/ogg", "audio/flac", "audio/x-wav"]
+    }
+}
+
+struct Player {
+    state: Arc<Mutex<MprisState>>,
+    position: Arc<AtomicI64>,
+    commands: Sender<MprisCommand>,
+}
+
+impl Player {
+    fn send(&self, command: MprisCommand) {
+        let _ = self.commands.send(command);
+    }
+
+    fn snapshot(&self) -> MprisState {
+        self.state.lock().map(|state| state.clone()).unwrap_or_default()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn play_pause(&self) {
+        self.send(MprisCommand::PlayPause);
+    }
+
+    fn play(&self) {
+        self.send(MprisCommand::Play);
+    }
+
+    fn pause(&self) {
+        self.send(MprisCommand::Pause);
+    }
+
+    fn stop(&self) {
+        self.send(MprisCommand::Stop);
+    }
+
+    fn next(&self) {
+        self.send(MprisCommand::Next);
+    }
+
+    fn previous(&self) {
+        self.send(MprisCommand::Previous);
+    }
+
+    fn seek(&self, offset: i64) {
+        self.send(MprisCommand::Seek(offset));
+    }
+
+    fn set_position(&self, track_id: ObjectPath<'_>, position: i64) {
+        // Spesifikasi: abaikan kalau track sudah berganti
+        let current = format!("/org/terminus/track/{}", self.snapshot().track);
+        if track_id.as_str() == current {
+            self.send(MprisCommand::SetPosition(position));
+        }
+    }
+
+    fn open_uri(&self, uri: String) -> fdo::Result<()> {
+        if uri.trim().is_empty() {
+            return Err(fdo::Error::InvalidArgs(String::from("empty URI")));
+        }
+        self.send(MprisCommand::OpenUri(uri));
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn playback_status(&self) -> &str {
+        self.snapshot().status.as_str()
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        self.snapshot().rate
+    }
+
+    #[zbus(property)]
+    fn set_rate(&mut self, rate: f64) {
+        // Spesifikasi: rate 0.0 diperlakukan seperti Pause
+        if rate <= 0.0 {
+            self.send(MprisCommand::Pause);
+        } else {
+            self.send(MprisCommand::SetRate(rate));
+        }
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn minimum_rate(&self) -> f64 {
+        MIN_SPEED
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn maximum_rate(&self) -> f64 {
+        MAX_SPEED
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        self.snapshot().metadata()
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.snapshot().volume
+    }
+
+    #[zbus(property)]
+    fn set_volume(&mut self, volume: f64) {
+        self.send(MprisCommand::SetVolume(volume.clamp(0.0, 1.0)));
+    }
+
+    /// Nilai terakhir dari `PositionPoller`; client menghitung sendiri di antaranya lewat `Rate`
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> i64 {
+        if self.snapshot().status == PlaybackStatus::Stopped {
+            0
+        } else {
+            self.position.load(Ordering::Relaxed)
+        }
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.snapshot().status != PlaybackStatus::Stopped
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,64 @@
+// src/tools/mpv_ipc.rs
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+use serde_json::{json, Value};
+
+/// Socket yang dipakai mpv lewat `--input-ipc-server`: satu per proses
+/// terminus di `$XDG_RUNTIME_DIR` (hanya bisa diakses user ini), supaya
+/// instance kedua atau user lain tidak bisa memakai atau merebutnya
+pub fn socket_path() -> &'static Path {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+    PATH.get_or_init(|| {
+        let name = format!("terminus-mpv-{}.sock", std::process::id());
+        match dirs::runtime_dir() {
+            Some(dir) => dir.join(name),
+            None => {
+                let user = std::env::var("USER").unwrap_or_else(|_| String::from("user"));
+                std::env::temp_dir().join(format!("terminus-{}-mpv-{}.sock", user, std::process::id()))
+            }
+        }
+    })
+}
+
+const TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Kirim satu command JSON ke mpv dan kembalikan field `data` dari balasannya.
+/// Event (`{"event": ...}`) yang datang di tengah jalan dilewati.
+pub fn command(args: Value) -> io::Result<Value> {
+    let stream = UnixStream::connect(socket_path())?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let request = json!({ "command": args, "request_id": 1 });
+    (&stream).write_all(format!("{}\n", request).as_bytes())?;
+
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "mpv closed the IPC socket"));
+        }
+        let reply: Value = serde_json::from_str(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if reply.get("event").is_some() {
+            continue;
+        }
+        return match reply.get("error").and_then(Value::as_str) {
+            Some("success") | None => Ok(reply.get("data").cloned().unwrap_or(Value::Null)),
+            Some(error) => Err(io::Error::other(format!("mpv: {}", error))),
+        };
+    }
+}
+
+pub fn get_property(name: &str) -> io::Result<Value> {
+    command(json!(["get_property", name]))
+}
+
+pub fn set_property(name: &str, value: Value) -> io::Result<Value> {
+    command(json!(["set_property", name, value]))
+}
@@ -0,0 +1,178 @@
+// src/tools/mpv_output.rs
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read},
+    process::Child,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+/// Jumlah baris output mentah yang disimpan per lagu
+const MAX_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutputLine {
+    pub stream: Stream,
+    pub text: String,
+}
+
+/// Ambil stdout/stderr proses (mpv, yt-dlp; harus di-spawn dengan
+/// `Stdio::piped()`) dan baca di thread sendiri. Channel putus setelah kedua stream mencapai EOF.
+pub fn capture(child: &mut Child) -> Receiver<OutputLine> {
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        thread::spawn(move || read_stream(stdout, Stream::Stdout, &tx));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        thread::spawn(move || read_stream(stderr, Stream::Stderr, &tx));
+    }
+    rx
+}
+
+fn read_stream(reader: impl Read, stream: Stream, tx: &Sender<OutputLine>) {
+    let reader = BufReader::new(reader);
+    for chunk in reader.split(b'\n') {
+        let Ok(chunk) = chunk else { break };
+        // Status line mpv ditimpa dengan `\r`; simpan tiap versinya sebagai baris
+        for part in String::from_utf8_lossy(&chunk).split('\r') {
+            let text = part.trim_end();
+            if text.is_empty() {
+                continue;
+            }
+            if tx.send(OutputLine { stream, text: text.to_string() }).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Pesan error mpv/ytdl_hook yang dikenal, diterjemahkan untuk user
+pub fn classify(line: &str) -> Option<&'static str> {
+    let lower = line.to_lowercase();
+    let has = |needle: &str| lower.contains(needle);
+
+    if has("youtube-dl failed: not found") || has("yt-dlp: not found") || has("youtube-dl: not found") {
+        Some("yt-dlp (or youtube-dl) is not installed; it is needed for YouTube and similar sites")
+    } else if has("could not open/initialize audio device") {
+        Some("the audio output device could not be opened; choose another one in Settings")
+    } else if has("unsupported url") {
+        Some("unsupported URL: yt-dlp does not know this site")
+    } else if has("no protocol handler found") || has("failed to recognize file format") {
+        Some("unsupported URL or file format")
+    } else if has("403") && (has("forbidden") || has("http error")) {
+        Some("access denied (HTTP 403); the link may have expired or be region-locked")
+    } else if has("404") && (has("not found") || has("http error")) {
+        Some("not found (HTTP 404)")
+    } else if has("video unavailable") || has("private video") {
+        Some("video is unavailable or private")
+    } else if has("sign in to confirm") {
+        Some("the site requires signing in (age or bot check)")
+    } else if has("no such file or directory") && has("failed to open") {
+        Some("file not found")
+    } else {
+        None
+    }
+}
+
+/// Output mpv untuk satu lagu, ditampilkan di panel diagnostik
+#[derive(Default)]
+pub struct TrackLog {
+    pub lines: VecDeque<OutputLine>,
+    /// Error yang dikenali dari output, tanpa duplikat
+    pub errors: Vec<&'static str>,
+}
+
+impl TrackLog {
+    /// Mulai putaran baru: output lama diberi pembatas, bukan dihapus
+    pub fn start_run(&mut self, source: &str) {
+        self.errors.clear();
+        if !self.lines.is_empty() {
+            self.push_line(OutputLine { stream: Stream::Stdout, text: String::new() });
+        }
+        self.push_line(OutputLine { stream: Stream::Stdout, text: format!("--- mpv {}", source) });
+    }
+
+    /// Simpan satu baris; kembalikan error yang baru dikenali
+    pub fn push(&mut self, line: OutputLine) -> Option<&'static str> {
+        let error = classify(&line.text).filter(|error| !self.errors.contains(error));
+        if let Some(error) = error {
+            self.errors.push(error);
+        }
+        self.push_line(line);
+        error
+    }
+
+    fn push_line(&mut self, line: OutputLine) {
+        self.lines.push_back(line);
+        if self.lines.len() > MAX_LINES {
+            self.lines.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_errors_are_classified() {
+        assert_eq!(
+            classify("[ytdl_hook] youtube-dl failed: not found or not enough permissions"),
+            Some("yt-dlp (or youtube-dl) is not installed; it is needed for YouTube and similar sites")
+        );
+        assert_eq!(
+            classify("[ao] Could not open/initialize audio device -> no sound."),
+            Some("the audio output device could not be opened; choose another one in Settings")
+        );
+        assert_eq!(
+            classify("[ytdl_hook] ERROR: Unsupported URL: https://example.com/"),
+            Some("unsupported URL: yt-dlp does not know this site")
+        );
+        assert_eq!(
+            classify("[ffmpeg] No protocol handler found to open URL gopher://x"),
+            Some("unsupported URL or file format")
+        );
+        assert_eq!(
+            classify("Failed to recognize file format."),
+            Some("unsupported URL or file format")
+        );
+        assert_eq!(
+            classify("[ffmpeg] https: HTTP error 403 Forbidden"),
+            Some("access denied (HTTP 403); the link may have expired or be region-locked")
+        );
+        assert_eq!(
+            classify("[ytdl_hook] ERROR: unable to download webpage: HTTP Error 404: Not Found"),
+            Some("not found (HTTP 404)")
+        );
+        assert_eq!(
+            classify("[ytdl_hook] ERROR: [youtube] abc: Video unavailable"),
+            Some("video is unavailable or private")
+        );
+        assert_eq!(
+            classify("[ytdl_hook] ERROR: [youtube] abc: Private video. Sign in if you've been granted access"),
+            Some("video is unavailable or private")
+        );
+        assert_eq!(
+            classify("ERROR: [youtube] abc: Sign in to confirm your age"),
+            Some("the site requires signing in (age or bot check)")
+        );
+        assert_eq!(
+            classify("Failed to open /music/a.mp3: No such file or directory"),
+            Some("file not found")
+        );
+    }
+
+    #[test]
+    fn unknown_lines_are_not_classified() {
+        assert_eq!(classify(" (+) Audio --aid=1 (mp3 2ch 44100Hz)"), None);
+        assert_eq!(classify("A: 00:01:02 / 00:03:04 (33%)"), None);
+        assert_eq!(classify(""), None);
+    }
+}
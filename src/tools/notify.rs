@@ -0,0 +1,139 @@
+// src/tools/notify.rs
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+};
+use zbus::{blocking::{Connection, Proxy}, zvariant::{Structure, Value}};
+
+use crate::app::{expand_tilde, Song};
+use crate::tools::album_art::{self, Image};
+
+const DESTINATION: &str = "org.freedesktop.Notifications";
+const OBJECT_PATH: &str = "/org/freedesktop/Notifications";
+const INTERFACE: &str = "org.freedesktop.Notifications";
+const EXPIRE_TIMEOUT_MS: i32 = 5000;
+
+/// Sisi terpanjang cover yang dikirim ke daemon notifikasi, dalam pixel
+const COVER_SIZE: u32 = 256;
+
+/// Tombol yang ditekan user di notifikasi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyAction {
+    Next,
+    Pause,
+}
+
+struct TrackNotification {
+    summary: String,
+    body: String,
+    /// File lokal yang cover-nya dicari di thread sender
+    source: Option<PathBuf>,
+}
+
+/// Desktop notification lewat `org.freedesktop.Notifications`. Pemanggilan
+/// D-Bus dilakukan di thread sendiri supaya UI tidak ikut menunggu daemon.
+pub struct Notifier {
+    requests: Sender<TrackNotification>,
+    pub actions: Receiver<NotifyAction>,
+}
+
+impl Notifier {
+    pub fn start() -> zbus::Result<Self> {
+        let connection = Connection::session()?;
+        let last_id = Arc::new(AtomicU32::new(0));
+        let (request_tx, request_rx) = mpsc::channel::<TrackNotification>();
+        let (action_tx, action_rx) = mpsc::channel();
+
+        // Sender: notifikasi baru menggantikan yang lama (`replaces_id`)
+        let sender_connection = connection.clone();
+        let sender_id = Arc::clone(&last_id);
+        thread::spawn(move || {
+            for request in request_rx {
+                if let Ok(id) = send(&sender_connection, sender_id.load(Ordering::Relaxed), &request) {
+                    sender_id.store(id, Ordering::Relaxed);
+                }
+            }
+        });
+
+        // Listener: `ActionInvoked` hanya diteruskan untuk notifikasi milik kita
+        let proxy = Proxy::new(&connection, DESTINATION, OBJECT_PATH, INTERFACE)?;
+        let signals = proxy.receive_signal("ActionInvoked")?;
+        thread::spawn(move || {
+            for message in signals {
+                let Ok((id, key)) = message.body().deserialize::<(u32, String)>() else { continue };
+                if id == 0 || id != last_id.load(Ordering::Relaxed) {
+                    continue;
+                }
+                let action = match key.as_str() {
+                    "next" => NotifyAction::Next,
+                    "pause" => NotifyAction::Pause,
+                    _ => continue,
+                };
+                if action_tx.send(action).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { requests: request_tx, actions: action_rx })
+    }
+
+    /// Umumkan lagu yang baru mulai diputar
+    pub fn track_changed(&self, song: &Song, title: &str) {
+        let _ = self.requests.send(TrackNotification {
+            summary: title.to_string(),
+            body: song.artist.clone(),
+            source: song.path.as_deref().filter(|path| !path.trim().is_empty()).map(expand_tilde),
+        });
+    }
+}
+
+fn send(connection: &Connection, replaces_id: u32, request: &TrackNotification) -> zbus::Result<u32> {
+    let mut hints: HashMap<&str, Value> = HashMap::new();
+    hints.insert("category", Value::from("x-terminus.track"));
+    if let Some(source) = &request.source {
+        match album_art::find(source) {
+            Ok(Some(image)) => {
+                hints.insert("image-data", image_data(&image));
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("no notification cover for {}: {}", source.display(), e),
+        }
+    }
+
+    let reply = connection.call_method(
+        Some(DESTINATION),
+        OBJECT_PATH,
+        Some(INTERFACE),
+        "Notify",
+        &(
+            "terminus",
+            replaces_id,
+            "audio-x-generic",
+            request.summary.as_str(),
+            request.body.as_str(),
+            vec!["next", "Next", "pause", "Pause"],
+            hints,
+            EXPIRE_TIMEOUT_MS,
+        ),
+    )?;
+    reply.body().deserialize::<u32>()
+}
+
+/// Hint `image-data` (`iiibiiay`): pixel dikirim langsung, jadi cover yang
+/// tertanam di file tidak perlu ditulis ke disk dulu
+fn image_data(image: &Image) -> Value<'static> {
+    let scale = f64::min(1.0, COVER_SIZE as f64 / image.width.max(image.height) as f64);
+    let image = image.resize(
+        (image.width as f64 * scale).round() as u32,
+        (image.height as f64 * scale).round() as u32,
+    );
+    let (width, height) = (image.width as i32, image.height as i32);
+    Value::from(Structure::from((width, height, width * 3, false, 8i32, 3i32, image.rgb().to_vec())))
+}
@@ -0,0 +1,586 @@
+// src/tools/podcast.rs
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+    thread,
+    time::Duration,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+use crate::tools::date::{civil_from_days, days_from_civil};
+
+const USER_AGENT: &str = concat!("terminus/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Episode {
+    /// `guid`/`id` dari feed, atau URL enclosure kalau tidak ada
+    pub guid: String,
+    pub title: String,
+    /// Unix timestamp (detik)
+    pub published: Option<i64>,
+    /// Durasi dalam detik
+    pub duration: Option<u64>,
+    pub enclosure_url: String,
+    #[serde(default)]
+    pub played: bool,
+    /// Posisi resume dalam detik
+    #[serde(default)]
+    pub position: f64,
+    #[serde(default)]
+    pub download_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Podcast {
+    pub feed_url: String,
+    pub title: String,
+    pub episodes: Vec<Episode>,
+}
+
+impl Podcast {
+    pub fn unplayed(&self) -> usize {
+        self.episodes.iter().filter(|episode| !episode.played).count()
+    }
+
+    /// Gabungkan hasil fetch terbaru: state lokal (played, posisi, file
+    /// download) dipertahankan berdasarkan `guid`.
+    pub fn merge(&mut self, fresh: Podcast) {
+        let mut episodes = fresh.episodes;
+        for episode in &mut episodes {
+            if let Some(old) = self.episodes.iter().find(|old| old.guid == episode.guid) {
+                episode.played = old.played;
+                episode.position = old.position;
+                episode.download_path = old.download_path.clone();
+            }
+        }
+        self.title = fresh.title;
+        self.episodes = episodes;
+    }
+}
+
+/// Semua langganan podcast, disimpan di data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PodcastStore {
+    pub podcasts: Vec<Podcast>,
+}
+
+impl PodcastStore {
+    pub fn data_dir() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("terminus"))
+    }
+
+    pub fn load() -> Self {
+        Self::data_dir()
+            .and_then(|dir| fs::read_to_string(dir.join("podcasts.json")).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = Self::data_dir().ok_or("Data directory not found")?;
+        storage::atomic_write(&dir.join("podcasts.json"), serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Tambah podcast baru atau perbarui yang sudah ada (berdasarkan URL feed)
+    pub fn upsert(&mut self, fresh: Podcast) {
+        match self.podcasts.iter_mut().find(|p| p.feed_url == fresh.feed_url) {
+            Some(existing) => existing.merge(fresh),
+            None => self.podcasts.push(fresh),
+        }
+    }
+
+    pub fn episode_mut(&mut self, feed_url: &str, guid: &str) -> Option<&mut Episode> {
+        self.podcasts
+            .iter_mut()
+            .find(|p| p.feed_url == feed_url)?
+            .episodes
+            .iter_mut()
+            .find(|e| e.guid == guid)
+    }
+}
+
+/// Event dari worker background ke event loop
+#[derive(Debug)]
+pub enum PodcastEvent {
+    FeedLoaded(Result<Podcast, String>),
+    DownloadProgress { guid: String, downloaded: u64, total: Option<u64> },
+    DownloadFinished { feed_url: String, guid: String, path: PathBuf },
+    DownloadFailed { guid: String, error: String },
+}
+
+/// Fetch dan parse feed di thread terpisah
+pub fn spawn_fetch(feed_url: String, tx: Sender<PodcastEvent>) {
+    thread::spawn(move || {
+        let result = fetch_feed(&feed_url).map_err(|e| format!("{}: {}", feed_url, e));
+        let _ = tx.send(PodcastEvent::FeedLoaded(result));
+    });
+}
+
+pub fn fetch_feed(feed_url: &str) -> Result<Podcast, Box<dyn std::error::Error>> {
+    let body = agent().get(feed_url).call()?.into_string()?;
+    Ok(parse_feed(&body, feed_url)?)
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+/// Parse RSS 2.0 atau Atom. Tag dicocokkan dengan nama lokal saja supaya
+/// `itunes:duration`, `media:content`, dll. tidak bergantung prefix namespace.
+pub fn parse_feed(xml: &str, feed_url: &str) -> Result<Podcast, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let root = doc.root_element();
+
+    let (container, item_tag) = match root.tag_name().name() {
+        "rss" => (child(root, "channel").ok_or("RSS feed without <channel>")?, "item"),
+        "feed" => (root, "entry"),
+        other => return Err(format!("unsupported feed type <{}>", other)),
+    };
+
+    let title = child_text(container, "title").unwrap_or_else(|| feed_url.to_string());
+    let episodes = container
+        .children()
+        .filter(|node| node.tag_name().name() == item_tag)
+        .filter_map(parse_item)
+        .collect();
+
+    Ok(Podcast { feed_url: feed_url.to_string(), title, episodes })
+}
+
+fn parse_item(item: roxmltree::Node) -> Option<Episode> {
+    // RSS: <enclosure url=...>, Atom: <link rel="enclosure" href=...>
+    let enclosure_url = item.children().find_map(|node| match node.tag_name().name() {
+        "enclosure" => node.attribute("url").map(str::to_string),
+        "link" if node.attribute("rel") == Some("enclosure") => node.attribute("href").map(str::to_string),
+        _ => None,
+    })?;
+
+    let guid = child_text(item, "guid")
+        .or_else(|| child_text(item, "id"))
+        .unwrap_or_else(|| enclosure_url.clone());
+    let title = child_text(item, "title").unwrap_or_else(|| String::from("Untitled episode"));
+    let published = child_text(item, "pubDate")
+        .or_else(|| child_text(item, "published"))
+        .or_else(|| child_text(item, "updated"))
+        .and_then(|date| parse_date(&date));
+    let duration = child_text(item, "duration")
+        .and_then(|d| parse_duration(&d))
+        .or_else(|| {
+            item.children()
+                .find(|node| node.tag_name().name() == "content")
+                .and_then(|node| node.attribute("duration"))
+                .and_then(parse_duration)
+        });
+
+    Some(Episode {
+        guid,
+        title,
+        published,
+        duration,
+        enclosure_url,
+        played: false,
+        position: 0.0,
+        download_path: None,
+    })
+}
+
+fn child<'a, 'input>(node: roxmltree::Node<'a, 'input>, name: &str) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|c| c.tag_name().name() == name)
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// `itunes:duration` bisa berupa detik (`3600`), `MM:SS`, atau `HH:MM:SS`
+pub fn parse_duration(text: &str) -> Option<u64> {
+    let mut total = 0u64;
+    for part in text.trim().split(':') {
+        let value: f64 = part.trim().parse().ok()?;
+        // Nilai aneh dari feed jangan sampai overflow
+        total = total.checked_mul(60)?.checked_add(value as u64)?;
+    }
+    Some(total)
+}
+
+/// Parse tanggal RFC 2822 (RSS) atau RFC 3339 (Atom) ke Unix timestamp
+pub fn parse_date(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if text.as_bytes().get(4) == Some(&b'-') {
+        parse_rfc3339(text)
+    } else {
+        parse_rfc2822(text)
+    }
+}
+
+fn parse_rfc3339(text: &str) -> Option<i64> {
+    // 2003-12-13T18:30:02Z / 2003-12-13T18:30:02.25+01:00
+    let year: i64 = text.get(0..4)?.parse().ok()?;
+    let month: u32 = text.get(5..7)?.parse().ok()?;
+    let day: u32 = text.get(8..10)?.parse().ok()?;
+    if text.len() <= 10 {
+        return Some(days_from_civil(year, month, day) * 86400);
+    }
+    let hour: i64 = text.get(11..13)?.parse().ok()?;
+    let minute: i64 = text.get(14..16)?.parse().ok()?;
+    let second: i64 = text.get(17..19)?.parse().ok()?;
+    let zone = text[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = parse_offset(zone)?;
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+fn parse_rfc2822(text: &str) -> Option<i64> {
+    // [Tue, ]10 Jun 2003 04:00[:00] GMT|+0000
+    let text = text.split_once(',').map_or(text, |(_, rest)| rest);
+    let mut parts = text.split_whitespace();
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = match parts.next()?.get(..3)?.to_ascii_lowercase().as_str() {
+        "jan" => 1, "feb" => 2, "mar" => 3, "apr" => 4, "may" => 5, "jun" => 6,
+        "jul" => 7, "aug" => 8, "sep" => 9, "oct" => 10, "nov" => 11, "dec" => 12,
+        _ => return None,
+    };
+    let mut year: i64 = parts.next()?.parse().ok()?;
+    if year < 100 {
+        year += if year < 50 { 2000 } else { 1900 };
+    }
+    let mut time = parts.next().unwrap_or("00:00:00").split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next().unwrap_or("0").parse().ok()?;
+    let second: i64 = time.next().unwrap_or("0").parse().ok()?;
+    let offset = parse_offset(parts.next().unwrap_or("GMT")).unwrap_or(0);
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Offset zona waktu dalam detik: `Z`, `GMT`, `+0700`, `+07:00`, `-05:00`
+fn parse_offset(zone: &str) -> Option<i64> {
+    match zone {
+        "" | "Z" | "z" | "GMT" | "UT" | "UTC" => return Some(0),
+        "EST" => return Some(-5 * 3600),
+        "EDT" => return Some(-4 * 3600),
+        "CST" => return Some(-6 * 3600),
+        "CDT" => return Some(-5 * 3600),
+        "MST" => return Some(-7 * 3600),
+        "MDT" => return Some(-6 * 3600),
+        "PST" => return Some(-8 * 3600),
+        "PDT" => return Some(-7 * 3600),
+        _ => {}
+    }
+    let sign = match zone.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits: String = zone[1..].chars().filter(char::is_ascii_digit).collect();
+    let hours: i64 = digits.get(0..2)?.parse().ok()?;
+    let minutes: i64 = digits.get(2..4).unwrap_or("0").parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Format Unix timestamp sebagai `YYYY-MM-DD`
+pub fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Format detik sebagai `H:MM:SS` atau `M:SS`
+pub fn format_duration(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Folder download untuk sebuah podcast
+pub fn download_dir(podcast_title: &str) -> Option<PathBuf> {
+    Some(PodcastStore::data_dir()?.join("podcasts").join(sanitize(podcast_title)))
+}
+
+/// Nama file aman dari judul bebas
+pub fn sanitize(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || " -_.()".contains(c) { c } else { '_' })
+        .collect();
+    let cleaned = cleaned.trim().trim_start_matches('.');
+    if cleaned.is_empty() { String::from("untitled") } else { cleaned.chars().take(120).collect() }
+}
+
+/// Nama file download: tanggal rilis untuk urutan, judul, dan hash `guid`
+/// supaya episode berjudul sama (mis. "Trailer") tidak saling menimpa
+pub fn file_name(episode: &Episode, extension: &str) -> String {
+    let hash = format!("{:x}", md5::compute(episode.guid.as_bytes()));
+    let title = sanitize(&episode.title);
+    match episode.published {
+        Some(published) => format!("{} {} [{}].{}", format_date(published), title, &hash[..8], extension),
+        None => format!("{} [{}].{}", title, &hash[..8], extension),
+    }
+}
+
+/// Hapus semua file episode yang sudah di-download, beserta foldernya kalau
+/// jadi kosong. Mengembalikan jumlah file yang dihapus.
+pub fn remove_downloads(podcast: &Podcast) -> Result<usize, String> {
+    let mut removed = 0;
+    let mut failed = Vec::new();
+    for path in podcast.episodes.iter().filter_map(|episode| episode.download_path.as_deref()) {
+        let path = Path::new(path);
+        match fs::remove_file(path) {
+            Ok(()) => removed += 1,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => failed.push(format!("{}: {}", path.display(), e)),
+        }
+        // Gagal kalau folder masih berisi file lain; itu memang disengaja
+        if let Some(dir) = path.parent() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+    match failed.first() {
+        None => Ok(removed),
+        Some(first) if failed.len() == 1 => Err(first.clone()),
+        Some(first) => Err(format!("{} (and {} more)", first, failed.len() - 1)),
+    }
+}
+
+/// Download episode di thread terpisah. File ditulis ke `.part` lalu di-rename
+/// setelah selesai, jadi file yang setengah jadi tidak pernah dianggap lengkap.
+pub fn spawn_download(feed_url: String, episode: Episode, dir: PathBuf, tx: Sender<PodcastEvent>) {
+    thread::spawn(move || {
+        let guid = episode.guid.clone();
+        match download(&episode, &dir, &guid, &tx) {
+            Ok(path) => {
+                let _ = tx.send(PodcastEvent::DownloadFinished { feed_url, guid, path });
+            }
+            Err(e) => {
+                let _ = tx.send(PodcastEvent::DownloadFailed { guid, error: e.to_string() });
+            }
+        }
+    });
+}
+
+fn download(episode: &Episode, dir: &Path, guid: &str, tx: &Sender<PodcastEvent>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let extension = url::Url::parse(&episode.enclosure_url)
+        .ok()
+        .and_then(|url| {
+            let last = url.path_segments()?.next_back()?.to_string();
+            last.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase())
+        })
+        .filter(|ext| ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| String::from("mp3"));
+    let path = dir.join(file_name(episode, &extension));
+
+    let response = agent().get(&episode.enclosure_url).call()?;
+    let total = response.header("content-length").and_then(|v| v.parse().ok());
+    save_download(response.into_reader(), &path, total, guid, tx)?;
+    Ok(path)
+}
+
+/// Tulis isi download ke `<path>.part` lalu rename ke `path`. `.part` yang
+/// gagal dihapus; download ulang selalu mulai dari awal.
+fn save_download(
+    reader: impl Read,
+    path: &Path,
+    total: Option<u64>,
+    guid: &str,
+    tx: &Sender<PodcastEvent>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let extension = path.extension().map(|ext| ext.to_string_lossy().into_owned()).unwrap_or_default();
+    let part = path.with_extension(format!("{}.part", extension));
+    let result = write_part(reader, &part, total, guid, tx).and_then(|()| fs::rename(&part, path).map_err(Into::into));
+    if result.is_err() {
+        let _ = fs::remove_file(&part);
+    }
+    result
+}
+
+fn write_part(
+    mut reader: impl Read,
+    part: &Path,
+    total: Option<u64>,
+    guid: &str,
+    tx: &Sender<PodcastEvent>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = fs::File::create(part)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+    let mut last_report = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        file.write_all(&buf[..n])?;
+        downloaded += n as u64;
+        // Laporkan progress tiap ~256 KiB supaya channel tidak banjir
+        if downloaded - last_report >= 256 * 1024 {
+            last_report = downloaded;
+            let _ = tx.send(PodcastEvent::DownloadProgress { guid: guid.to_string(), downloaded, total });
+        }
+    }
+    file.sync_all()?;
+    if let Some(total) = total
+        && downloaded < total
+    {
+        return Err(format!("download incomplete ({} of {} bytes)", downloaded, total).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+  <channel>
+    <title>Test Cast</title>
+    <item>
+      <title>Episode 2</title>
+      <guid>ep-2</guid>
+      <pubDate>Tue, 10 Jun 2003 04:00:00 GMT</pubDate>
+      <itunes:duration>01:02:03</itunes:duration>
+      <enclosure url="http://127.0.0.1/ep2.mp3" type="audio/mpeg" length="1"/>
+    </item>
+    <item>
+      <title>No audio</title>
+      <guid>text-only</guid>
+    </item>
+    <item>
+      <title><![CDATA[Episode <1>]]></title>
+      <enclosure url="http://127.0.0.1/ep1.mp3" type="audio/mpeg" length="1"/>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">
+  <title>Atom Cast</title>
+  <entry>
+    <id>urn:ep:1</id>
+    <title>First</title>
+    <published>2003-12-13T18:30:02Z</published>
+    <link rel="alternate" href="http://127.0.0.1/page"/>
+    <link rel="enclosure" href="http://127.0.0.1/first.ogg"/>
+    <media:content url="http://127.0.0.1/first.ogg" duration="95"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_rss_items_with_enclosures() {
+        let podcast = parse_feed(RSS, "http://127.0.0.1/feed.xml").unwrap();
+        assert_eq!(podcast.title, "Test Cast");
+        // Item tanpa enclosure dilewati
+        assert_eq!(podcast.episodes.len(), 2);
+
+        let first = &podcast.episodes[0];
+        assert_eq!(first.guid, "ep-2");
+        assert_eq!(first.title, "Episode 2");
+        assert_eq!(first.published, Some(1_055_217_600));
+        assert_eq!(first.duration, Some(3723));
+        assert_eq!(first.enclosure_url, "http://127.0.0.1/ep2.mp3");
+
+        // Tanpa guid, URL enclosure dipakai sebagai identitas
+        let second = &podcast.episodes[1];
+        assert_eq!(second.title, "Episode <1>");
+        assert_eq!(second.guid, "http://127.0.0.1/ep1.mp3");
+        assert_eq!(second.published, None);
+    }
+
+    #[test]
+    fn parses_atom_entries() {
+        let podcast = parse_feed(ATOM, "http://127.0.0.1/atom.xml").unwrap();
+        assert_eq!(podcast.title, "Atom Cast");
+        let episode = &podcast.episodes[0];
+        assert_eq!(episode.guid, "urn:ep:1");
+        assert_eq!(episode.enclosure_url, "http://127.0.0.1/first.ogg");
+        assert_eq!(episode.published, Some(1_071_340_202));
+        assert_eq!(episode.duration, Some(95));
+    }
+
+    #[test]
+    fn rejects_non_feed_documents() {
+        assert!(parse_feed("<html><body/></html>", "x").is_err());
+        assert!(parse_feed("<rss version=\"2.0\"/>", "x").is_err());
+        assert!(parse_feed("not xml", "x").is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("3600"), Some(3600));
+        assert_eq!(parse_duration("12:34"), Some(754));
+        assert_eq!(parse_duration("1:02:03"), Some(3723));
+        assert_eq!(parse_duration(" 95.5 "), Some(95));
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("18446744073709551615:59"), None);
+    }
+
+    #[test]
+    fn parses_rfc2822_dates() {
+        assert_eq!(parse_date("Tue, 10 Jun 2003 04:00:00 GMT"), Some(1_055_217_600));
+        assert_eq!(parse_date("10 Jun 2003 04:00 +0000"), Some(1_055_217_600));
+        assert_eq!(parse_date("Tue, 10 Jun 2003 11:00:00 +0700"), Some(1_055_217_600));
+        assert_eq!(parse_date("Tue, 10 Jun 2003 00:00:00 EDT"), Some(1_055_217_600));
+        assert_eq!(parse_date("Tue, 10 Jun 03 04:00:00 GMT"), Some(1_055_217_600));
+        assert_eq!(parse_date("Tue, 10 Foo 2003 04:00:00 GMT"), None);
+    }
+
+    #[test]
+    fn parses_rfc3339_dates() {
+        assert_eq!(parse_date("2003-12-13T18:30:02Z"), Some(1_071_340_202));
+        assert_eq!(parse_date("2003-12-13T19:30:02.25+01:00"), Some(1_071_340_202));
+        assert_eq!(parse_date("2024-02-29"), Some(1_709_164_800));
+    }
+
+    #[test]
+    fn same_title_episodes_get_distinct_file_names() {
+        let podcast = parse_feed(RSS, "http://127.0.0.1/feed.xml").unwrap();
+        let mut first = podcast.episodes[0].clone();
+        let mut second = podcast.episodes[1].clone();
+        first.title = String::from("Trailer");
+        second.title = String::from("Trailer");
+
+        let first_name = file_name(&first, "mp3");
+        assert!(first_name.starts_with("2003-06-10 Trailer ["), "{}", first_name);
+        assert!(file_name(&second, "mp3").starts_with("Trailer ["));
+        assert_ne!(first_name, file_name(&second, "mp3"));
+
+        second.published = first.published;
+        assert_ne!(first_name, file_name(&second, "mp3"));
+        assert_eq!(first_name, file_name(&first, "mp3"));
+    }
+
+    #[test]
+    fn failed_downloads_leave_no_part_file() {
+        let dir = std::env::temp_dir().join(format!("terminus-podcast-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Episode [abcd1234].mp3");
+        let (tx, _rx) = std::sync::mpsc::channel();
+
+        let short = save_download(&b"only part of it"[..], &path, Some(1000), "guid", &tx);
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        let complete = save_download(&b"all of it"[..], &path, Some(9), "guid", &tx);
+        let content = fs::read_to_string(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(short.unwrap_err().to_string(), "download incomplete (15 of 1000 bytes)");
+        assert_eq!(leftovers, 0);
+        assert!(complete.is_ok());
+        assert_eq!(content.unwrap(), "all of it");
+    }
+}
@@ -0,0 +1,183 @@
+// src/tools/radio.rs
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::storage;
+use crate::tools::mpv_ipc;
+
+/// Seberapa sering metadata stream ditanyakan ke mpv
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const HISTORY_PER_STATION: usize = 200;
+
+/// Update dari thread pembaca metadata ke event loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadioEvent {
+    /// mpv sudah membuka stream; `station` diambil dari metadata `icy-name`
+    Connected { station: Option<String> },
+    /// `icy-title` berubah
+    Title(String),
+}
+
+/// Membaca metadata ICY/Shoutcast yang sudah di-parse mpv lewat IPC, jadi
+/// tidak perlu koneksi kedua ke server radio. Reconnect stream ditangani
+/// event loop dengan menjalankan ulang mpv; watcher ini tetap jalan.
+pub struct IcyWatcher {
+    stop: Arc<AtomicBool>,
+    pub events: Receiver<RadioEvent>,
+}
+
+impl IcyWatcher {
+    pub fn spawn() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        let thread_stop = Arc::clone(&stop);
+        thread::spawn(move || watch(&tx, &thread_stop));
+        Self { stop, events: rx }
+    }
+}
+
+impl Drop for IcyWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn watch(tx: &Sender<RadioEvent>, stop: &AtomicBool) {
+    let mut connected = false;
+    let mut last_title: Option<String> = None;
+    while !stop.load(Ordering::Relaxed) {
+        // `metadata` baru ada setelah mpv berhasil membuka stream
+        match mpv_ipc::get_property("metadata") {
+            Ok(metadata) => {
+                let mut events = Vec::new();
+                if !connected {
+                    connected = true;
+                    events.push(RadioEvent::Connected { station: metadata_text(metadata.get("icy-name")) });
+                }
+                let title = mpv_ipc::get_property("metadata/by-key/icy-title").ok();
+                if let Some(title) = metadata_text(title.as_ref())
+                    && last_title.as_ref() != Some(&title)
+                {
+                    last_title = Some(title.clone());
+                    events.push(RadioEvent::Title(title));
+                }
+                if events.into_iter().any(|event| tx.send(event).is_err()) {
+                    // Receiver sudah di-drop, tidak ada yang mendengarkan lagi
+                    break;
+                }
+            }
+            // mpv belum siap, atau sedang dijalankan ulang untuk reconnect
+            Err(_) => connected = false,
+        }
+
+        // Tidur sedikit-sedikit supaya stop tetap responsif
+        let mut slept = Duration::ZERO;
+        while slept < POLL_INTERVAL && !stop.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(100));
+            slept += Duration::from_millis(100);
+        }
+    }
+}
+
+/// Nilai metadata berupa teks yang tidak kosong
+fn metadata_text(value: Option<&Value>) -> Option<String> {
+    let text = value?.as_str()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Satu judul yang pernah diputar sebuah stasiun
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RadioPlay {
+    pub title: String,
+    /// Unix timestamp (detik)
+    pub played_at: u64,
+}
+
+/// Riwayat pergantian lagu per stasiun, disimpan di data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RadioHistory {
+    stations: HashMap<String, Vec<RadioPlay>>,
+}
+
+impl RadioHistory {
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("terminus").join("radio_history.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path().ok_or("Data directory not found")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        storage::atomic_write(&path, serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, station: &str, title: &str) {
+        let played_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let plays = self.stations.entry(station.to_string()).or_default();
+        plays.push(RadioPlay { title: title.to_string(), played_at });
+        if plays.len() > HISTORY_PER_STATION {
+            plays.drain(..plays.len() - HISTORY_PER_STATION);
+        }
+    }
+
+    /// Judul terakhir dulu
+    pub fn recent(&self, station: &str) -> impl Iterator<Item = &RadioPlay> {
+        self.stations.get(station).into_iter().flat_map(|plays| plays.iter().rev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn metadata_text_ignores_missing_and_blank_values() {
+        assert_eq!(metadata_text(Some(&json!("  Artist - Song "))), Some(String::from("Artist - Song")));
+        assert_eq!(metadata_text(Some(&json!("Rock 'n' Roll"))), Some(String::from("Rock 'n' Roll")));
+        assert_eq!(metadata_text(Some(&json!("   "))), None);
+        assert_eq!(metadata_text(Some(&json!(null))), None);
+        assert_eq!(metadata_text(Some(&json!(42))), None);
+        assert_eq!(metadata_text(None), None);
+    }
+
+    #[test]
+    fn history_keeps_latest_plays_per_station() {
+        let mut history = RadioHistory::default();
+        for i in 0..HISTORY_PER_STATION + 5 {
+            history.record("http://radio.example/a", &format!("Song {}", i));
+        }
+        history.record("http://radio.example/b", "Other");
+
+        let titles: Vec<&str> = history.recent("http://radio.example/a").map(|play| play.title.as_str()).collect();
+        assert_eq!(titles.len(), HISTORY_PER_STATION);
+        assert_eq!(titles.first(), Some(&"Song 204"));
+        assert_eq!(titles.last(), Some(&"Song 5"));
+        assert_eq!(history.recent("http://radio.example/b").count(), 1);
+        assert_eq!(history.recent("http://radio.example/c").count(), 0);
+    }
+}
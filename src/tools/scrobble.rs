@@ -0,0 +1,351 @@
+// src/tools/scrobble.rs
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::config::{ScrobbleApi, ScrobbleConfig};
+
+const USER_AGENT: &str = concat!("terminus/", env!("CARGO_PKG_VERSION"));
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const MIN_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+/// Lagu yang lebih pendek dari ini tidak di-scrobble (aturan Last.fm/ListenBrainz)
+pub const MIN_TRACK_LENGTH: Duration = Duration::from_secs(30);
+/// Listen dihitung setelah setengah lagu atau 4 menit, mana yang lebih dulu
+pub const MAX_LISTEN_THRESHOLD: Duration = Duration::from_secs(4 * 60);
+
+/// Satu listen yang akan dikirim
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Listen {
+    pub title: String,
+    pub artist: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    /// Unix timestamp (detik) saat lagu mulai diputar
+    pub listened_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+impl Listen {
+    pub fn new(title: &str, artist: &str, url: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            url: url.to_string(),
+            listened_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            duration: None,
+        }
+    }
+}
+
+/// Menghitung waktu dengar lagu yang sedang diputar. Waktu pause tidak dihitung.
+#[derive(Debug, Clone)]
+pub struct ListenTracker {
+    pub listen: Listen,
+    pub played: Duration,
+    pub submitted: bool,
+    duration_checked: Option<Instant>,
+}
+
+impl ListenTracker {
+    pub fn new(listen: Listen) -> Self {
+        Self { listen, played: Duration::ZERO, submitted: false, duration_checked: None }
+    }
+
+    /// Durasi belum diketahui dan sudah waktunya bertanya lagi ke mpv (maks. 1x per detik)
+    pub fn needs_duration(&mut self) -> bool {
+        if self.listen.duration.is_some()
+            || self.duration_checked.is_some_and(|at| at.elapsed() < Duration::from_secs(1))
+        {
+            return false;
+        }
+        self.duration_checked = Some(Instant::now());
+        true
+    }
+
+    /// Tambah waktu dengar; `true` kalau listen baru saja memenuhi syarat
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        self.played += elapsed;
+        if self.submitted {
+            return false;
+        }
+        let threshold = match self.listen.duration.map(Duration::from_secs) {
+            Some(length) if length < MIN_TRACK_LENGTH => return false,
+            Some(length) => (length / 2).min(MAX_LISTEN_THRESHOLD),
+            None => MAX_LISTEN_THRESHOLD,
+        };
+        if self.played >= threshold {
+            self.submitted = true;
+            return true;
+        }
+        false
+    }
+}
+
+/// Hasil pengiriman dari worker ke event loop
+#[derive(Debug, Clone)]
+pub enum ScrobbleEvent {
+    Sent { count: usize },
+    /// Listen ditolak server secara permanen dan dibuang dari antrean
+    Rejected { title: String, error: String },
+    Failed { error: String, retry_in: Duration, queued: usize },
+}
+
+enum Job {
+    NowPlaying(Listen),
+    Submit(Listen),
+}
+
+/// Antrean listen yang belum terkirim, disimpan di data directory supaya
+/// tetap ada walau terminus ditutup saat offline
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScrobbleQueue {
+    pub listens: Vec<Listen>,
+}
+
+impl ScrobbleQueue {
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("terminus").join("scrobble_queue.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path().ok_or("Data directory not found")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Worker scrobbling di background. Listen masuk antrean dulu, lalu dikirim
+/// berurutan; kalau gagal, dicoba lagi dengan backoff eksponensial.
+pub struct Scrobbler {
+    jobs: Sender<Job>,
+    pub events: Receiver<ScrobbleEvent>,
+}
+
+impl Scrobbler {
+    pub fn spawn(config: ScrobbleConfig) -> Self {
+        let (job_tx, job_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::spawn(move || run(config, job_rx, event_tx));
+        Self { jobs: job_tx, events: event_rx }
+    }
+
+    pub fn now_playing(&self, listen: Listen) {
+        let _ = self.jobs.send(Job::NowPlaying(listen));
+    }
+
+    pub fn submit(&self, listen: Listen) {
+        let _ = self.jobs.send(Job::Submit(listen));
+    }
+}
+
+fn run(config: ScrobbleConfig, jobs: Receiver<Job>, events: Sender<ScrobbleEvent>) {
+    let mut queue = ScrobbleQueue::load();
+    let mut backoff = MIN_BACKOFF;
+    // Antrean lama dari sesi sebelumnya langsung dicoba
+    let mut retry_at = Some(Instant::now());
+
+    loop {
+        let timeout = retry_at
+            .map(|at| at.saturating_duration_since(Instant::now()))
+            .unwrap_or(Duration::from_secs(3600));
+        match jobs.recv_timeout(timeout) {
+            Ok(Job::NowPlaying(listen)) => {
+                // "Now playing" tidak penting kalau gagal, jadi tidak diantrekan
+                let _ = send(&config, &listen, true);
+                continue;
+            }
+            Ok(Job::Submit(listen)) => {
+                queue.listens.push(listen);
+                let _ = queue.save();
+                if retry_at.is_some_and(|at| at > Instant::now()) {
+                    // Masih dalam backoff; tunggu giliran
+                    continue;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        retry_at = None;
+        if queue.listens.is_empty() {
+            continue;
+        }
+        let mut sent = 0;
+        while let Some(listen) = queue.listens.first().cloned() {
+            match send(&config, &listen, false) {
+                Ok(()) => {
+                    queue.listens.remove(0);
+                    sent += 1;
+                }
+                Err(SendError::Rejected(error)) => {
+                    queue.listens.remove(0);
+                    let _ = events.send(ScrobbleEvent::Rejected { title: listen.title, error });
+                }
+                Err(SendError::Retry(error)) => {
+                    retry_at = Some(Instant::now() + backoff);
+                    let _ = events.send(ScrobbleEvent::Failed {
+                        error,
+                        retry_in: backoff,
+                        queued: queue.listens.len(),
+                    });
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    break;
+                }
+            }
+        }
+        if retry_at.is_none() {
+            backoff = MIN_BACKOFF;
+        }
+        let _ = queue.save();
+        if sent > 0 {
+            let _ = events.send(ScrobbleEvent::Sent { count: sent });
+        }
+    }
+}
+
+enum SendError {
+    /// Server menolak listen ini (mis. data tidak valid); jangan dicoba lagi
+    Rejected(String),
+    /// Jaringan, rate limit, server error, atau token salah; coba lagi nanti
+    Retry(String),
+}
+
+fn send(config: &ScrobbleConfig, listen: &Listen, now_playing: bool) -> Result<(), SendError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build();
+
+    match config.api {
+        ScrobbleApi::ListenBrainz => {
+            let mut entry = json!({
+                "track_metadata": {
+                    "artist_name": listen.artist,
+                    "track_name": listen.title,
+                    "additional_info": { "media_player": "terminus" },
+                }
+            });
+            if !now_playing {
+                entry["listened_at"] = json!(listen.listened_at);
+            }
+            if !listen.url.is_empty() {
+                entry["track_metadata"]["additional_info"]["origin_url"] = json!(listen.url);
+            }
+            if let Some(duration) = listen.duration {
+                entry["track_metadata"]["additional_info"]["duration_ms"] = json!(duration * 1000);
+            }
+            let body = json!({
+                "listen_type": if now_playing { "playing_now" } else { "single" },
+                "payload": [entry],
+            });
+            agent
+                .post(&format!("{}/1/submit-listens", config.endpoint()))
+                .set("Authorization", &format!("Token {}", config.token))
+                .set("Content-Type", "application/json")
+                .send_string(&body.to_string())
+                .map(|_| ())
+                .map_err(http_error)
+        }
+        ScrobbleApi::LastFm => {
+            let mut params = BTreeMap::new();
+            params.insert("method", String::from(if now_playing { "track.updateNowPlaying" } else { "track.scrobble" }));
+            params.insert("artist", listen.artist.clone());
+            params.insert("track", listen.title.clone());
+            params.insert("api_key", config.api_key.clone());
+            params.insert("sk", config.token.clone());
+            if !now_playing {
+                params.insert("timestamp", listen.listened_at.to_string());
+            }
+            if let Some(duration) = listen.duration {
+                params.insert("duration", duration.to_string());
+            }
+            params.insert("api_sig", lastfm_signature(&params, &config.api_secret));
+            params.insert("format", String::from("json"));
+
+            let form: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+            let body = match agent.post(&format!("{}/", config.endpoint())).send_form(&form) {
+                Ok(response) => read_json(response),
+                // Body error Last.fm lebih jelas daripada status HTTP-nya
+                Err(ureq::Error::Status(code, response)) => match read_json(response) {
+                    Some(body) if body.get("error").is_some() => Some(body),
+                    _ => return Err(status_error(code)),
+                },
+                Err(e) => return Err(http_error(e)),
+            };
+            // Last.fm bisa menjawab 200 dengan field `error` di body
+            match body {
+                Some(body) if body.get("error").is_some() => Err(lastfm_error(&body)),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+fn read_json(response: ureq::Response) -> Option<Value> {
+    serde_json::from_str(&response.into_string().ok()?).ok()
+}
+
+fn http_error(error: ureq::Error) -> SendError {
+    match error {
+        ureq::Error::Status(code, _) => status_error(code),
+        ureq::Error::Transport(transport) => SendError::Retry(transport.to_string()),
+    }
+}
+
+fn status_error(code: u16) -> SendError {
+    match code {
+        400 | 413 | 422 => SendError::Rejected(format!("server returned {}", code)),
+        401 | 403 => SendError::Retry(String::from("authentication failed, check the token")),
+        _ => SendError::Retry(format!("server returned {}", code)),
+    }
+}
+
+/// `api_sig` Audioscrobbler: md5 dari semua parameter (urut nama) + secret
+fn lastfm_signature(params: &BTreeMap<&str, String>, secret: &str) -> String {
+    let mut raw = String::new();
+    for (key, value) in params {
+        raw.push_str(key);
+        raw.push_str(value);
+    }
+    raw.push_str(secret);
+    format!("{:x}", md5::compute(raw.as_bytes()))
+}
+
+fn lastfm_error(body: &Value) -> SendError {
+    let code = body.get("error").and_then(Value::as_i64).unwrap_or(0);
+    let message = body
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error")
+        .to_string();
+    match code {
+        // 11: service offline, 16: temporarily unavailable, 29: rate limit
+        11 | 16 | 29 => SendError::Retry(message),
+        // 4/9/10/26: auth, session key, API key bermasalah
+        4 | 9 | 10 | 26 => SendError::Retry(format!("authentication failed: {}", message)),
+        _ => SendError::Rejected(message),
+    }
+}
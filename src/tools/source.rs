@@ -0,0 +1,209 @@
+// src/tools/source.rs
+use std::{fmt, path::PathBuf};
+use url::Url;
+
+use crate::app::{expand_tilde, Song};
+
+/// Situs yang butuh yt-dlp untuk mendapatkan URL media sebenarnya
+const RESOLVABLE_HOSTS: &[&str] = &[
+    "youtube.com", "youtu.be", "youtube-nocookie.com", "soundcloud.com", "bandcamp.com",
+    "vimeo.com", "twitch.tv", "mixcloud.com", "nicovideo.jp", "bilibili.com", "dailymotion.com",
+];
+
+/// Ekstensi playlist/manifest yang biasanya dipakai radio dan live stream
+const STREAM_EXTENSIONS: &[&str] = &["pls", "m3u", "m3u8", "xspf", "asx"];
+
+/// Scheme yang selalu berupa stream
+const STREAM_SCHEMES: &[&str] = &["icy", "icyx", "rtsp", "rtmp", "rtmps", "mms", "mmsh", "srt", "udp"];
+
+/// Asal audio sebuah lagu, hasil validasi dari `Song.path` / `Song.url`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SongSource {
+    LocalFile(PathBuf),
+    HttpUrl(Url),
+    Stream(Url),
+    ResolvablePage(Url),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SourceError {
+    Empty,
+    InvalidUrl(String),
+    UnsupportedScheme(String),
+    MissingFile(PathBuf),
+    NotAFile(PathBuf),
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceError::Empty => write!(f, "no URL or path given"),
+            SourceError::InvalidUrl(url) => write!(f, "invalid URL: {}", url),
+            SourceError::UnsupportedScheme(scheme) => write!(f, "unsupported scheme `{}`", scheme),
+            SourceError::MissingFile(path) => write!(f, "file not found: {}", path.display()),
+            SourceError::NotAFile(path) => write!(f, "not a file: {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+impl SongSource {
+    /// Klasifikasikan input dari form atau playlist. Input tanpa scheme
+    /// dianggap path lokal; file lokal harus ada di disk.
+    pub fn parse(input: &str) -> Result<Self, SourceError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(SourceError::Empty);
+        }
+
+        let url = match Url::parse(input) {
+            Ok(url) => url,
+            Err(url::ParseError::RelativeUrlWithoutBase) => return Self::local(expand_tilde(input)),
+            Err(_) => return Err(SourceError::InvalidUrl(input.to_string())),
+        };
+
+        match url.scheme() {
+            "file" => {
+                let path = url
+                    .to_file_path()
+                    .map_err(|_| SourceError::InvalidUrl(input.to_string()))?;
+                Self::local(path)
+            }
+            "http" | "https" => {
+                let host = url
+                    .host_str()
+                    .ok_or_else(|| SourceError::InvalidUrl(input.to_string()))?
+                    .to_ascii_lowercase();
+                let extension = url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    .and_then(|last| last.rsplit_once('.'))
+                    .map(|(_, ext)| ext.to_ascii_lowercase());
+
+                if RESOLVABLE_HOSTS
+                    .iter()
+                    .any(|known| host == *known || host.ends_with(&format!(".{}", known)))
+                {
+                    Ok(SongSource::ResolvablePage(url))
+                } else if extension.as_deref().is_some_and(|ext| STREAM_EXTENSIONS.contains(&ext)) {
+                    Ok(SongSource::Stream(url))
+                } else {
+                    // File media langsung, atau URL lain yang biar mpv tentukan sendiri
+                    Ok(SongSource::HttpUrl(url))
+                }
+            }
+            scheme if STREAM_SCHEMES.contains(&scheme) => Ok(SongSource::Stream(url)),
+            // Windows drive letter seperti `C:\music` terbaca sebagai scheme satu huruf
+            scheme if scheme.len() == 1 => Self::local(PathBuf::from(input)),
+            scheme => Err(SourceError::UnsupportedScheme(scheme.to_string())),
+        }
+    }
+
+    fn local(path: PathBuf) -> Result<Self, SourceError> {
+        if !path.exists() {
+            Err(SourceError::MissingFile(path))
+        } else if !path.is_file() {
+            Err(SourceError::NotAFile(path))
+        } else {
+            Ok(SongSource::LocalFile(path))
+        }
+    }
+
+    /// Sumber sebuah lagu: `path` lebih diutamakan daripada `url`
+    pub fn of(song: &Song) -> Result<Self, SourceError> {
+        match &song.path {
+            Some(path) if !path.trim().is_empty() => Self::parse(path),
+            _ => Self::parse(&song.url),
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, SongSource::LocalFile(_))
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SongSource::LocalFile(_) => "local file",
+            SongSource::HttpUrl(_) => "http",
+            SongSource::Stream(_) => "stream",
+            SongSource::ResolvablePage(_) => "yt-dlp",
+        }
+    }
+
+    /// Argumen positional untuk mpv. Selalu ditaruh setelah `--`, jadi
+    /// nilai yang diawali `-` tidak pernah dibaca sebagai opsi.
+    pub fn to_mpv_arg(&self) -> String {
+        match self {
+            SongSource::LocalFile(path) => path.to_string_lossy().into_owned(),
+            SongSource::HttpUrl(url) | SongSource::Stream(url) | SongSource::ResolvablePage(url) => {
+                url.to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_prefixed_input_is_a_local_path() {
+        assert_eq!(
+            SongSource::parse("--script=evil.lua"),
+            Err(SourceError::MissingFile(PathBuf::from("--script=evil.lua")))
+        );
+
+        let dir = std::env::temp_dir().join(format!("terminus-source-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("-loud.mp3");
+        std::fs::write(&file, b"").unwrap();
+        let parsed = SongSource::parse(file.to_str().unwrap());
+        let not_a_file = SongSource::parse(dir.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(parsed, Ok(SongSource::LocalFile(file)));
+        assert_eq!(not_a_file, Err(SourceError::NotAFile(dir)));
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        assert_eq!(SongSource::parse(""), Err(SourceError::Empty));
+        assert_eq!(SongSource::parse(" \t\n"), Err(SourceError::Empty));
+    }
+
+    #[test]
+    fn urls_are_classified_by_scheme_host_and_extension() {
+        let kind = |input: &str| SongSource::parse(input).map(|source| source.kind());
+        assert_eq!(kind("https://example.com/song.mp3"), Ok("http"));
+        assert_eq!(kind("http://example.com/listen"), Ok("http"));
+        assert_eq!(kind("https://radio.example.com/live.M3U8"), Ok("stream"));
+        assert_eq!(kind("http://example.com/station.pls"), Ok("stream"));
+        assert_eq!(kind("rtsp://example.com/live"), Ok("stream"));
+        assert_eq!(kind("icy://example.com:8000/stream"), Ok("stream"));
+        assert_eq!(kind("https://www.youtube.com/watch?v=abc"), Ok("yt-dlp"));
+        assert_eq!(kind("https://music.YouTube.com/watch?v=abc"), Ok("yt-dlp"));
+        assert_eq!(kind("https://artist.bandcamp.com/track/x"), Ok("yt-dlp"));
+        // Host yang hanya mirip tidak ikut dianggap situs yt-dlp
+        assert_eq!(kind("https://notyoutube.com/watch?v=abc"), Ok("http"));
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected() {
+        assert_eq!(
+            SongSource::parse("ftp://example.com/a.mp3"),
+            Err(SourceError::UnsupportedScheme(String::from("ftp")))
+        );
+        assert_eq!(SongSource::parse("http://"), Err(SourceError::InvalidUrl(String::from("http://"))));
+    }
+
+    #[test]
+    fn missing_local_file_is_reported() {
+        let path = "/nonexistent/terminus/song.flac";
+        assert_eq!(SongSource::parse(path), Err(SourceError::MissingFile(PathBuf::from(path))));
+        assert_eq!(
+            SongSource::parse(&format!("file://{}", path)),
+            Err(SourceError::MissingFile(PathBuf::from(path)))
+        );
+    }
+}
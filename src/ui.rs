@@ -1,13 +1,23 @@
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout, Alignment},
+    layout::{Constraint, Direction, Layout, Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap, Gauge},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap, Gauge},
     Frame,
 };
 
-use crate::app::{App, AppState, MusicPlayerMode};
+use std::time::{SystemTime, UNIX_EPOCH};
+use indicatif::HumanDuration;
+
+use crate::app::{song_source, App, AppState, DownloadPrompt, MusicPlayerMode, PromptKind, Song};
+use crate::logging;
+use crate::toast::Level;
+use crate::tools::album_art::{self, Protocol};
+use crate::tools::cache::AudioCache;
+use crate::tools::downloader::{Job, JobState};
+use crate::tools::mpv_output::{OutputLine, Stream};
+use crate::tools::podcast;
 
 pub fn draw<B: Backend>(f: &mut Frame<B>, app: &App) {
     match app.state {
@@ -16,9 +26,50 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, app: &App) {
         AppState::VideoPlayer => draw_feature_screen(f, "🎥 Video Player", Color::Magenta, get_video_features()),
         AppState::FileTools => draw_feature_screen(f, "📁 File Tools", Color::Blue, get_file_tools_features()),
         AppState::ComingSoon => draw_feature_screen(f, "🧪 Coming Soon", Color::Yellow, get_coming_soon_features()),
+        AppState::Messages => draw_messages(f, app),
+        AppState::Logs => draw_logs(f, app),
+        AppState::Settings => draw_settings(f, app),
+        AppState::Downloads => draw_downloads(f, app),
+    }
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::Info => Color::Cyan,
+        Level::Warn => Color::Yellow,
+        Level::Error => Color::Red,
     }
 }
 
+/// Baris status: toast yang masih tampil dulu, lalu error simpan yang belum teratasi
+fn status_line(app: &App) -> Spans<'_> {
+    match (app.toasts.current(), &app.save_error) {
+        (Some(toast), _) if toast.level == Level::Info => {
+            Spans::from(vec![Span::styled(toast.message.as_str(), Style::default().fg(Color::Cyan))])
+        }
+        (Some(toast), _) => Spans::from(vec![Span::styled(
+            format!("{} {}", toast.level.icon(), toast.message),
+            Style::default().fg(level_color(toast.level)),
+        )]),
+        (None, Some(error)) => Spans::from(vec![Span::styled(format!("⚠ {}", error), Style::default().fg(Color::Red))]),
+        (None, None) => Spans::from(vec![Span::raw("")]),
+    }
+}
+
+/// Status cache offline sebuah lagu URL: di-pin, tersimpan, atau sedang diunduh
+fn offline_badge<'a>(app: &App, song: &Song) -> Span<'a> {
+    let Some(key) = AudioCache::key(song) else { return Span::raw("") };
+    let cached = app.cache.cached_path(&key).is_some();
+    let (text, color) = match (app.cache.is_pinned(&key), cached, app.cache.is_downloading(&key)) {
+        (_, false, true) => ("  ⬇ downloading", Color::Yellow),
+        (true, true, _) => ("  📌 offline", Color::Green),
+        (true, false, _) => ("  📌 not downloaded", Color::Red),
+        (false, true, _) => ("  💾 cached", Color::DarkGray),
+        (false, false, false) => return Span::raw(""),
+    };
+    Span::styled(text, Style::default().fg(color))
+}
+
 // === Shared Layout Helper ===
 fn get_layout(area: tui::layout::Rect) -> Vec<tui::layout::Rect> {
     Layout::default()
@@ -106,8 +157,17 @@ fn draw_main_menu<B: Backend>(f: &mut Frame<B>, app: &App) {
 
     f.render_widget(menu, chunks[1]);
 
-    // Status bar
-    let status = Paragraph::new(" Navigate with ↑ ↓  |  Press [Enter] to select  |  Press [q] to quit ")
+    // Status bar; toast yang masih aktif menggantikan petunjuk tombol
+    let status_text = match app.toasts.current() {
+        Some(toast) => Spans::from(vec![Span::styled(
+            format!("{} {}", toast.level.icon(), toast.message),
+            Style::default().fg(level_color(toast.level)),
+        )]),
+        None => Spans::from(vec![Span::raw(
+            " Navigate with ↑ ↓  |  Press [Enter] to select  |  [!] Messages  |  Press [q] to quit ",
+        )]),
+    };
+    let status = Paragraph::new(status_text)
         .style(Style::default().fg(Color::White))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
@@ -123,6 +183,14 @@ fn draw_music_player<B: Backend>(f: &mut Frame<B>, app: &App) {
         MusicPlayerMode::AddSong => draw_add_song_mode(f, app),
         MusicPlayerMode::EditSong => draw_edit_song_mode(f, app),
         MusicPlayerMode::DeleteConfirm => draw_delete_confirm_mode(f, app),
+        MusicPlayerMode::Prompt => draw_prompt_mode(f, app),
+        MusicPlayerMode::Duplicates => draw_duplicates_mode(f, app),
+        MusicPlayerMode::Podcasts => draw_podcasts_mode(f, app),
+        MusicPlayerMode::Unsubscribe => draw_unsubscribe_mode(f, app),
+        MusicPlayerMode::Episodes => draw_episodes_mode(f, app),
+        MusicPlayerMode::ReloadConflict => draw_reload_conflict_mode(f, app),
+        MusicPlayerMode::Diagnostics => draw_diagnostics_mode(f, app),
+        MusicPlayerMode::ImportPreview => draw_import_preview_mode(f, app),
     }
 }
 
@@ -133,7 +201,7 @@ fn draw_browse_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
         .constraints([
             Constraint::Length(3),  // Title
             Constraint::Min(10),    // Playlist
-            Constraint::Length(5),  // Controls
+            Constraint::Length(6),  // Controls
         ])
         .split(f.size());
 
@@ -159,18 +227,31 @@ fn draw_browse_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     } else {
         let items: Vec<ListItem> = app.playlist.iter().enumerate().map(|(i, song)| {
         let is_selected = i == app.selected_song_index;
-        let prefix = if is_selected { "▶ " } else { "  " };
-        let artist_text = format!("by {}", song.artist); // Simpan dalam variabel
+        let is_marked = app.marked.contains(&i);
+        let radio_icon = if song.radio { "📻 " } else { "" };
+        let prefix = match (is_selected, is_marked) {
+            (true, true) => "▶●",
+            (true, false) => "▶ ",
+            (false, true) => " ●",
+            (false, false) => "  ",
+        };
+        let mut artist_text = format!("by {}", song.artist); // Simpan dalam variabel
+        if let Some(duration) = song.duration {
+            artist_text.push_str(&format!(" · {}", podcast::format_duration(duration)));
+        }
         
         ListItem::new(vec![
             Spans::from(vec![
-                Span::raw(prefix),
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::raw(radio_icon),
                 Span::styled(
                     &song.title,
                     Style::default()
-                        .fg(if is_selected { Color::Yellow } else { Color::White })
+                        .fg(if is_selected { Color::Yellow } else if is_marked { Color::Cyan } else { Color::White })
                         .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() })
                 ),
+                offline_badge(app, song),
             ]),
             Spans::from(vec![
                 Span::raw("    "),
@@ -178,22 +259,37 @@ fn draw_browse_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
                     artist_text, // Gunakan variabel yang sudah dibuat
                     Style::default().fg(if is_selected { Color::Gray } else { Color::DarkGray })
                 ),
+                Span::styled(
+                    app.source_error(song).map(|e| format!("  ⚠ {}", e)).unwrap_or_default(),
+                    Style::default().fg(Color::Red)
+                ),
             ]),
         ])
     }).collect();
 
+        let mut list_title = String::from(" Playlist ");
+        if !app.marked.is_empty() {
+            list_title.push_str(&format!("- {} marked ", app.marked.len()));
+        }
+        if !app.queue.is_empty() {
+            list_title.push_str(&format!("- {} queued ", app.queue.len()));
+        }
+
         let playlist = List::new(items)
             .block(Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green))
-                .title(" Playlist "));
+                .title(list_title));
         
-        f.render_widget(playlist, chunks[1]);
+        // ListState supaya list ikut scroll mengikuti lagu terpilih
+        let mut state = ListState::default();
+        state.select(Some(app.selected_song_index));
+        f.render_stateful_widget(playlist, chunks[1], &mut state);
     }
 
     // Controls
     let controls = vec![
-        Spans::from(vec![Span::raw("")]),
+        status_line(app),
         Spans::from(vec![
             Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
             Span::raw(" Navigate   "),
@@ -207,9 +303,39 @@ fn draw_browse_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
             Span::raw(" Edit song   "),
             Span::styled(" [d]", Style::default().fg(Color::Yellow)),
             Span::raw(" Delete song   "),
+            Span::styled(" [J/K]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Move   "),
+            Span::styled(" [u/Ctrl+R]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Undo/Redo   "),
             Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
             Span::raw(" Back   "),
         ]),
+        Spans::from(vec![
+            Span::styled(" [Space/V/*]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Mark/Range/Invert   "),
+            Span::styled(" [Q]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Queue   "),
+            Span::styled(" [m]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Move to playlist   "),
+            Span::styled(" [x]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Export M3U   "),
+            Span::styled(" [I]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Import yt-dlp   "),
+            Span::styled(" [t/T]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Set artist/title   "),
+            Span::styled(" [D]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Find duplicates   "),
+            Span::styled(" [R]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Radio   "),
+            Span::styled(" [O]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Pin offline   "),
+            Span::styled(" [P]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Podcasts   "),
+            Span::styled(" [!]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Messages   "),
+            Span::styled(" [i]", Style::default().fg(Color::Yellow)),
+            Span::raw(" mpv output   "),
+        ]),
     ];
 
     let controls_widget = Paragraph::new(controls)
@@ -228,9 +354,9 @@ fn draw_now_playing_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
         .margin(2)
         .constraints([
             Constraint::Length(3),  // Title
-            Constraint::Length(7),  // Now Playing
+            Constraint::Length(9),  // Now Playing
             Constraint::Min(6),     // Visualization (placeholder)
-            Constraint::Length(5),  // Controls
+            Constraint::Length(6),  // Controls
         ])
         .split(f.size());
 
@@ -243,16 +369,28 @@ fn draw_now_playing_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     f.render_widget(title, chunks[0]);
 
     // Now Playing Information
-    let song_info = if let Some(song) = app.playlist.get(app.selected_song_index) {
+    let song = app.playing_song.as_ref().or(app.playlist.get(app.selected_song_index));
+    let song_info = if let Some(song) = song {
+        // Stasiun radio: tampilkan nama stasiun dan judul dari metadata ICY
+        let (title_label, title_text, artist_label, artist_text) = if song.radio {
+            (
+                "Station: ",
+                app.station_name.clone().unwrap_or_else(|| song.title.clone()),
+                "On air: ",
+                app.stream_title.clone().unwrap_or_else(|| String::from("…")),
+            )
+        } else {
+            ("Title: ", song.title.clone(), "Artist: ", song.artist.clone())
+        };
         vec![
             Spans::from(vec![Span::raw("")]),
             Spans::from(vec![
-                Span::styled("Title: ", Style::default().fg(Color::Gray)),
-                Span::styled(&song.title, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Span::styled(title_label, Style::default().fg(Color::Gray)),
+                Span::styled(title_text, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
             ]),
             Spans::from(vec![
-                Span::styled("Artist: ", Style::default().fg(Color::Gray)),
-                Span::styled(&song.artist, Style::default().fg(Color::White)),
+                Span::styled(artist_label, Style::default().fg(Color::Gray)),
+                Span::styled(artist_text, Style::default().fg(Color::White)),
             ]),
             Spans::from(vec![
                 Span::styled("Status: ", Style::default().fg(Color::Gray)),
@@ -260,6 +398,47 @@ fn draw_now_playing_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
                     if app.music_playing { "Playing" } else { "Paused" },
                     Style::default().fg(if app.music_playing { Color::Green } else { Color::Yellow })
                 ),
+                Span::styled(
+                    app.playing_source_kind.map(|kind| format!(" ({})", kind)).unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray)
+                ),
+                offline_badge(app, song),
+            ]),
+            Spans::from(vec![
+                Span::styled("Speed: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("{:.1}x", app.speed),
+                    Style::default().fg(if app.speed == 1.0 { Color::White } else { Color::Cyan })
+                ),
+                Span::styled("   Loop: ", Style::default().fg(Color::Gray)),
+                match (app.loop_a, app.loop_b) {
+                    (Some(a), Some(b)) => Span::styled(
+                        format!("{} – {}", podcast::format_duration(a as u64), podcast::format_duration(b as u64)),
+                        Style::default().fg(Color::Cyan)
+                    ),
+                    (Some(a), None) => Span::styled(
+                        format!("{} – …", podcast::format_duration(a as u64)),
+                        Style::default().fg(Color::Yellow)
+                    ),
+                    _ => Span::styled("off", Style::default().fg(Color::White)),
+                },
+            ]),
+            match app.source_error(song) {
+                Some(error) => Spans::from(vec![
+                    Span::styled("Error: ", Style::default().fg(Color::Gray)),
+                    Span::styled(error, Style::default().fg(Color::Red)),
+                ]),
+                None => Spans::from(vec![Span::raw("")]),
+            },
+            Spans::from(vec![
+                Span::styled("Up next: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    match app.queue.front() {
+                        Some(next) => format!("{} ({} queued)", next.title, app.queue.len()),
+                        None => String::from("—"),
+                    },
+                    Style::default().fg(Color::White)
+                ),
             ]),
         ]
     } else {
@@ -299,19 +478,41 @@ fn draw_now_playing_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     
     f.render_widget(volume_gauge, volume_chunks[1]);
 
-    // Visual placeholder (would be replaced with actual visualization)
-    let visual_placeholder = Paragraph::new("Audio Visualization would appear here")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green)));
-    
-    f.render_widget(visual_placeholder, volume_chunks[2]);
+    // Radio: riwayat judul yang baru diputar stasiun ini
+    if let Some(song) = app.playing_song.as_ref().filter(|song| song.radio) {
+        let station = song_source(song);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let items: Vec<ListItem> = app.radio_history.recent(&station)
+            .map(|play| ListItem::new(Spans::from(vec![
+                Span::styled(format!("{:>8}  ", format_ago(now.saturating_sub(play.played_at))), Style::default().fg(Color::DarkGray)),
+                Span::raw(play.title.clone()),
+            ])))
+            .collect();
+
+        let history = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title(" Recently on air "));
+
+        f.render_widget(history, volume_chunks[2]);
+    } else if app.album_art.protocol.is_some() {
+        draw_album_art(f, app, volume_chunks[2]);
+    } else {
+        // Visual placeholder (would be replaced with actual visualization)
+        let visual_placeholder = Paragraph::new("Audio Visualization would appear here")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)));
+
+        f.render_widget(visual_placeholder, volume_chunks[2]);
+    }
 
     // Controls
     let controls = vec![
-        Spans::from(vec![Span::raw("")]),
+        status_line(app),
         Spans::from(vec![
             Span::styled(" [Space]", Style::default().fg(Color::Yellow)),
             Span::raw(" Play/Pause   "),
@@ -327,6 +528,26 @@ fn draw_now_playing_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
             Span::raw(" Back to playlist   "),
             Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
             Span::raw(" Stop   "),
+            Span::styled(" [N]", Style::default().fg(Color::Yellow)),
+            Span::raw(if app.config.notifications { " Notifications: on   " } else { " Notifications: off   " }),
+            Span::styled(" [!]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Messages   "),
+            Span::styled(" [i]", Style::default().fg(Color::Yellow)),
+            Span::raw(" mpv output   "),
+        ]),
+        Spans::from(vec![
+            Span::styled(" [ [/] ]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Slower/Faster   "),
+            Span::styled(" [=]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Normal speed   "),
+            Span::styled(" [l]", Style::default().fg(Color::Yellow)),
+            Span::raw(match (app.loop_a, app.loop_b) {
+                (None, _) => " Set loop start   ",
+                (Some(_), None) => " Set loop end   ",
+                (Some(_), Some(_)) => " Clear loop   ",
+            }),
+            Span::styled(" [o]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Output device   "),
         ]),
     ];
 
@@ -348,6 +569,9 @@ fn draw_edit_song_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     draw_song_form(f, app, "Edit Song", true);
 }
 
+/// Frame animasi untuk pekerjaan background yang sedang berjalan
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 fn draw_song_form<B: Backend>(f: &mut Frame<B>, app: &App, title: &str, is_edit: bool) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -371,8 +595,8 @@ fn draw_song_form<B: Backend>(f: &mut Frame<B>, app: &App, title: &str, is_edit:
     f.render_widget(header, chunks[0]);
 
     // Form Fields
-    let field_titles = ["Title", "Artist", "URL"];
-    let field_values = [&app.form_title, &app.form_artist, &app.form_url];
+    let field_titles = ["Title", "Artist", "URL or file path"];
+    let field_inputs = [&app.form_title, &app.form_artist, &app.form_url];
     
     for i in 0..3 {
         let is_active = app.form_field_index == i;
@@ -380,18 +604,39 @@ fn draw_song_form<B: Backend>(f: &mut Frame<B>, app: &App, title: &str, is_edit:
             .fg(if is_active { Color::Yellow } else { Color::White })
             .add_modifier(if is_active { Modifier::BOLD } else { Modifier::empty() });
         
-        let field = Paragraph::new(field_values[i].clone())
-            .style(field_style)
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_active { Color::Yellow } else { Color::Green }))
-                .title(format!(" {} {} ", 
-                    if is_active { ">" } else { " " },
-                    field_titles[i]
-                )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if is_active { Color::Yellow } else { Color::Green }))
+            .title(format!(" {} {} ", 
+                if is_active { ">" } else { " " },
+                field_titles[i]
+            ));
         
-        f.render_widget(field, chunks[i + 1]);
+        field_inputs[i].render(f, chunks[i + 1], block, field_style, is_active);
+    }
+
+    // Status metadata dan pesan (mis. peringatan duplikat) di area kosong bawah form
+    let mut status = Vec::new();
+    if let Some(lookup) = &app.metadata_lookup {
+        let frame = (lookup.started.elapsed().as_millis() / 100) as usize % SPINNER.len();
+        status.push(Spans::from(Span::styled(
+            format!("{} Looking up title, artist and duration...", SPINNER[frame]),
+            Style::default().fg(Color::Cyan),
+        )));
+    } else if let Some(duration) = app.form_duration {
+        status.push(Spans::from(vec![
+            Span::styled("Duration: ", Style::default().fg(Color::Gray)),
+            Span::raw(podcast::format_duration(duration)),
+        ]));
+    }
+    if let Some(toast) = app.toasts.current() {
+        status.push(Spans::from(Span::styled(
+            toast.message.as_str(),
+            Style::default().fg(level_color(toast.level)),
+        )));
     }
+    let status = Paragraph::new(status).alignment(Alignment::Center).wrap(Wrap { trim: true });
+    f.render_widget(status, chunks[4]);
 
     // Controls
     let action_text = if is_edit { "Update" } else { "Add" };
@@ -399,6 +644,8 @@ fn draw_song_form<B: Backend>(f: &mut Frame<B>, app: &App, title: &str, is_edit:
         Spans::from(vec![
             Span::styled(" [Tab]", Style::default().fg(Color::Yellow)),
             Span::raw(" Next field   "),
+            Span::styled(" [Ctrl+W/U]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Delete word/line   "),
             Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
             Span::raw(format!(" {} song   ", action_text)),
             Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
@@ -416,6 +663,60 @@ fn draw_song_form<B: Backend>(f: &mut Frame<B>, app: &App, title: &str, is_edit:
     f.render_widget(controls_widget, chunks[5]);
 }
 
+/// Cover lagu di NowPlaying. Half-block digambar di sini sebagai cell tui;
+/// kitty/sixel hanya dicatat area-nya dan ditulis main loop setelah frame selesai.
+fn draw_album_art<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let art = &app.album_art;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .title(" Cover ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(image) = &art.image else {
+        let message = if art.loading {
+            String::from("Loading cover art...")
+        } else if let Some(error) = &art.error {
+            format!("No cover art ({})", error)
+        } else {
+            String::from("No cover art")
+        };
+        let y = inner.y + inner.height.saturating_sub(1) / 2;
+        let line = Rect { y, height: inner.height.min(1), ..inner };
+        let text = Paragraph::new(message).style(Style::default().fg(Color::DarkGray)).alignment(Alignment::Center);
+        f.render_widget(text, line);
+        return;
+    };
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+    if art.protocol != Some(Protocol::Blocks) {
+        art.area.set(Some(inner));
+        return;
+    }
+
+    // Satu cell = dua pixel vertikal: `▀` dengan warna atas di fg, bawah di bg
+    let (cols, rows) = image.fit(inner, album_art::cell_size());
+    let scaled = image.resize(cols as u32, rows as u32 * 2);
+    let lines: Vec<Spans> = (0..rows as u32)
+        .map(|row| {
+            Spans::from((0..cols as u32).map(|x| {
+                let (tr, tg, tb) = scaled.pixel(x, row * 2);
+                let (br, bg, bb) = scaled.pixel(x, row * 2 + 1);
+                Span::styled("▀", Style::default().fg(Color::Rgb(tr, tg, tb)).bg(Color::Rgb(br, bg, bb)))
+            }).collect::<Vec<_>>())
+        })
+        .collect();
+    let target = Rect {
+        x: inner.x + (inner.width - cols) / 2,
+        y: inner.y + (inner.height - rows) / 2,
+        width: cols,
+        height: rows,
+    };
+    f.render_widget(Paragraph::new(lines), target);
+}
+
 fn draw_delete_confirm_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -441,10 +742,15 @@ fn draw_delete_confirm_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
         Spans::from(vec![Span::raw("")]),
         Spans::from(vec![
             Span::styled("⚠ WARNING: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled("You are about to delete this song from your playlist.", 
+            Span::styled(
+                if app.marked.is_empty() {
+                    String::from("You are about to delete this song from your playlist.")
+                } else {
+                    format!("You are about to delete {} marked songs from your playlist.", app.marked.len())
+                },
                 Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
         ]),
-        Spans::from(vec![Span::raw("This action cannot be undone.")]),
+        Spans::from(vec![Span::raw("You can undo this with [u] in the playlist.")]),
     ])
     .block(Block::default()
         .borders(Borders::ALL)
@@ -454,7 +760,20 @@ fn draw_delete_confirm_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     f.render_widget(warning, chunks[1]);
 
     // Song info
-    if let Some(song) = app.playlist.get(app.selected_song_index) {
+    if !app.marked.is_empty() {
+        let items: Vec<ListItem> = app.target_indices().into_iter()
+            .filter_map(|i| app.playlist.get(i))
+            .map(|song| ListItem::new(format!("{} - {}", song.title, song.artist)))
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" Songs to Delete "));
+
+        f.render_widget(list, chunks[2]);
+    } else if let Some(song) = app.playlist.get(app.selected_song_index) {
         let song_info = vec![
             Spans::from(vec![Span::raw("")]),
             Spans::from(vec![
@@ -499,6 +818,1062 @@ fn draw_delete_confirm_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
     f.render_widget(controls_widget, chunks[3]);
 }
 
+fn draw_reload_conflict_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Length(5),     // Warning
+            Constraint::Min(5),        // Differences
+            Constraint::Length(3),     // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new("🎵 Music Player - Playlist Changed on Disk")
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+
+    f.render_widget(title, chunks[0]);
+
+    let theirs = app.pending_reload.as_deref().unwrap_or_default();
+    let warning = Paragraph::new(vec![
+        Spans::from(vec![Span::raw("")]),
+        Spans::from(vec![
+            Span::styled("⚠ ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                "playlist.json was changed outside terminus while you have unsaved changes.",
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Spans::from(vec![Span::raw(format!(
+            "{} songs here, {} songs in the file.",
+            app.playlist.len(),
+            theirs.len()
+        ))]),
+    ])
+    .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)))
+    .alignment(Alignment::Center);
+
+    f.render_widget(warning, chunks[1]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[2]);
+    let only_in = |songs: &[Song], other: &[Song]| -> Vec<ListItem> {
+        songs
+            .iter()
+            .filter(|song| !other.contains(song))
+            .map(|song| ListItem::new(format!("{} - {}", song.title, song.artist)))
+            .collect()
+    };
+
+    let mine = List::new(only_in(&app.playlist, theirs)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Only in terminus "),
+    );
+    f.render_widget(mine, columns[0]);
+
+    let file = List::new(only_in(theirs, &app.playlist)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(" Only in playlist.json "),
+    );
+    f.render_widget(file, columns[1]);
+
+    let controls = vec![
+        Spans::from(vec![
+            Span::styled(" [m]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Merge both   "),
+            Span::styled(" [r]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Use file (undo with [u])   "),
+            Span::styled(" [k]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Keep mine, overwrite file   "),
+        ]),
+    ];
+
+    let controls_widget = Paragraph::new(controls)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Resolve "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls_widget, chunks[3]);
+}
+
+fn draw_diagnostics_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let song_title = app.diagnostics_song.as_ref().map(|song| song.title.as_str()).unwrap_or("");
+    let log = app.diagnostics_song.as_ref().and_then(|song| app.diagnostics.get(&song_source(song)));
+    let errors = log.map(|log| log.errors.as_slice()).unwrap_or_default();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),                                                  // Title
+            Constraint::Length(if errors.is_empty() { 0 } else { errors.len() as u16 + 2 }), // Errors
+            Constraint::Min(5),                                                     // Output
+            Constraint::Length(3),                                                  // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new(format!("🩺 mpv output - {}", song_title))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+    f.render_widget(title, chunks[0]);
+
+    if !errors.is_empty() {
+        let items: Vec<ListItem> = errors
+            .iter()
+            .map(|error| ListItem::new(Span::styled(format!("✖ {}", error), Style::default().fg(Color::Red))))
+            .collect();
+        let list = List::new(items).block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(" Problems "));
+        f.render_widget(list, chunks[1]);
+    }
+
+    // Tampilkan dari bawah seperti terminal; scroll dihitung dari baris terakhir
+    let lines: Vec<&OutputLine> = log.map(|log| log.lines.iter().collect()).unwrap_or_default();
+    let height = chunks[2].height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(app.diagnostics_scroll.min(lines.len().saturating_sub(height)));
+    let start = end.saturating_sub(height);
+    let items: Vec<ListItem> = lines[start..end].iter().map(|line| {
+        let style = if line.text.starts_with("--- mpv") {
+            Style::default().fg(Color::Cyan)
+        } else if line.stream == Stream::Stderr {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        ListItem::new(Span::styled(line.text.as_str(), style))
+    }).collect();
+
+    let output_title = if lines.is_empty() {
+        String::from(" No output yet: play this song first ")
+    } else {
+        format!(" Lines {}-{} of {} ", start + 1, end, lines.len())
+    };
+    let output = List::new(items).block(Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .title(output_title));
+    f.render_widget(output, chunks[2]);
+
+    let controls = Paragraph::new(Spans::from(vec![
+            Span::styled(" [↑/↓/PgUp/PgDn]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Scroll   "),
+            Span::styled(" [End]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Latest   "),
+            Span::styled(" [c]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Clear   "),
+            Span::styled(" [b]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back   "),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+    f.render_widget(controls, chunks[3]);
+}
+
+fn draw_prompt_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Length(3),     // Input
+            Constraint::Min(5),        // Hint
+            Constraint::Length(3),     // Controls
+        ])
+        .split(f.size());
+
+    let count = app.target_indices().len();
+    let (title, label) = match app.prompt_kind {
+        PromptKind::MoveToPlaylist => (format!("Move {} songs", count), "Target playlist"),
+        PromptKind::ExportM3u => (format!("Export {} songs", count), "M3U file path"),
+        PromptKind::SetArtist => (format!("Set artist for {} songs", count), "Artist"),
+        PromptKind::SetTitle => (format!("Set title for {} songs", count), "Title"),
+        PromptKind::SubscribeFeed => (String::from("Subscribe to podcast"), "RSS/Atom feed URL"),
+        PromptKind::ImportYtdlp => (String::from("Import yt-dlp playlist"), "JSON file path"),
+    };
+
+    let header = Paragraph::new(format!("🎵 Music Player - {}", title))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+
+    f.render_widget(header, chunks[0]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" > {} ", label));
+    app.prompt_input.render(f, chunks[1], block, Style::default().fg(Color::Yellow), true);
+
+    // Daftar playlist yang sudah ada sebagai petunjuk
+    let hint: Vec<Spans> = match app.prompt_kind {
+        PromptKind::MoveToPlaylist => {
+            let names = App::list_named_playlists();
+            if names.is_empty() {
+                vec![Spans::from(Span::raw("No other playlists yet. A new one will be created."))]
+            } else {
+                names.into_iter().map(|name| Spans::from(Span::raw(format!("• {}", name)))).collect()
+            }
+        }
+        PromptKind::ExportM3u => vec![Spans::from(Span::raw("Existing files will be overwritten."))],
+        PromptKind::SetArtist => vec![Spans::from(Span::raw("Titles are kept; only the artist changes."))],
+        PromptKind::SetTitle => vec![Spans::from(Span::raw("Artists are kept; only the title changes."))],
+        PromptKind::SubscribeFeed => vec![Spans::from(Span::raw("The feed is fetched in the background."))],
+        PromptKind::ImportYtdlp => vec![
+            Spans::from(Span::raw("Create the file with: yt-dlp --flat-playlist -J <playlist URL> > playlist.json")),
+            Spans::from(Span::raw("or pipe it in: yt-dlp --flat-playlist -J <URL> | terminus --import -")),
+            Spans::from(Span::raw("You can pick the songs and the target playlist on the next screen.")),
+        ],
+    };
+
+    let hint_widget = Paragraph::new(hint)
+        .style(Style::default().fg(Color::Gray))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(if app.prompt_kind == PromptKind::MoveToPlaylist { " Playlists " } else { " Note " }));
+
+    f.render_widget(hint_widget, chunks[2]);
+
+    let controls = Paragraph::new(Spans::from(vec![
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Confirm   "),
+            Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel   "),
+        ]))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Controls "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls, chunks[3]);
+}
+
+fn draw_duplicates_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Min(10),       // Groups
+            Constraint::Length(3),     // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new(format!("🎵 Music Player - {} Duplicate Groups", app.duplicate_groups.len()))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+
+    f.render_widget(title, chunks[0]);
+
+    // Tiap grup: lagu pertama dipertahankan, sisanya akan dihapus
+    let items: Vec<ListItem> = app.duplicate_groups.iter().enumerate().map(|(g, group)| {
+        let is_selected = g == app.selected_duplicate;
+        let mut lines = Vec::new();
+        for (n, &i) in group.iter().enumerate() {
+            let Some(song) = app.playlist.get(i) else { continue };
+            let (marker, color) = if n == 0 { ("keep  ", Color::Green) } else { ("merge ", Color::Red) };
+            lines.push(Spans::from(vec![
+                Span::raw(if is_selected && n == 0 { "▶ " } else { "  " }),
+                Span::styled(marker, Style::default().fg(color)),
+                Span::styled(
+                    format!("#{} {} - {}", i + 1, song.title, song.artist),
+                    Style::default()
+                        .fg(if is_selected { Color::Yellow } else { Color::White })
+                ),
+            ]));
+            lines.push(Spans::from(vec![
+                Span::raw("        "),
+                Span::styled(
+                    song.path.clone().unwrap_or_else(|| song.url.clone()),
+                    Style::default().fg(Color::DarkGray)
+                ),
+            ]));
+        }
+        ListItem::new(lines)
+    }).collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.selected_duplicate));
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Proposed Merges "));
+
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let controls = Paragraph::new(Spans::from(vec![
+            Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate   "),
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Merge group   "),
+            Span::styled(" [A]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Merge all   "),
+            Span::styled(" [s]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Skip   "),
+            Span::styled(" [b]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back   "),
+        ]))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Controls "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls, chunks[2]);
+}
+
+fn draw_import_preview_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let Some(preview) = &app.import else { return };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Min(5),        // Entries
+            Constraint::Length(3),     // Target playlist
+            Constraint::Length(4),     // Controls
+        ])
+        .split(f.size());
+
+    let source = preview.title.as_deref().unwrap_or("yt-dlp playlist");
+    let title = Paragraph::new(format!(
+        "🎵 Music Player - Import \"{}\" ({} of {} selected)",
+        source,
+        preview.selected_count(),
+        preview.entries.len()
+    ))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+
+    f.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = preview.entries.iter().enumerate().map(|(i, entry)| {
+        let is_cursor = i == preview.cursor;
+        let checkbox = if preview.selected[i] { "[x] " } else { "[ ] " };
+        let note = if !entry.available {
+            "  unavailable"
+        } else if preview.existing[i] {
+            "  already in playlist"
+        } else {
+            ""
+        };
+        let mut details = entry.song.artist.clone();
+        if let Some(duration) = entry.song.duration {
+            details.push_str(&format!(" · {}", podcast::format_duration(duration)));
+        }
+        ListItem::new(Spans::from(vec![
+            Span::styled(if is_cursor { "▶ " } else { "  " }, Style::default().fg(Color::Cyan)),
+            Span::styled(checkbox, Style::default().fg(if preview.selected[i] { Color::Green } else { Color::DarkGray })),
+            Span::styled(
+                &entry.song.title,
+                Style::default()
+                    .fg(if is_cursor { Color::Yellow } else if entry.available { Color::White } else { Color::DarkGray })
+                    .add_modifier(if is_cursor { Modifier::BOLD } else { Modifier::empty() })
+            ),
+            Span::styled(format!("  {}", details), Style::default().fg(Color::Gray)),
+            Span::styled(note, Style::default().fg(Color::Red)),
+        ]))
+    }).collect();
+
+    let mut state = ListState::default();
+    state.select(Some(preview.cursor));
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Entries "));
+
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let editing = preview.editing_target;
+    let target_title = if preview.target.value().trim().is_empty() && !editing {
+        String::from(" Target playlist: main playlist ")
+    } else {
+        format!(" {} Target playlist ", if editing { ">" } else { " " })
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if editing { Color::Yellow } else { Color::Green }))
+        .title(target_title);
+    preview.target.render(f, chunks[2], block, Style::default().fg(if editing { Color::Yellow } else { Color::White }), editing);
+
+    let keys = if editing {
+        vec![
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Confirm playlist (empty = main playlist)   "),
+            Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Done   "),
+        ]
+    } else {
+        vec![
+            Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate   "),
+            Span::styled(" [Space]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Select   "),
+            Span::styled(" [a]", Style::default().fg(Color::Yellow)),
+            Span::raw(" All/none   "),
+            Span::styled(" [t]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Target playlist   "),
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Import   "),
+            Span::styled(" [b]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back   "),
+        ]
+    };
+    let controls = Paragraph::new(vec![status_line(app), Spans::from(keys)])
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Controls "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls, chunks[3]);
+}
+
+fn draw_podcasts_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Min(10),       // Podcasts
+            Constraint::Length(4),     // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new("🎙 Music Player - Podcasts")
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+
+    f.render_widget(title, chunks[0]);
+
+    let mut list_title = String::from(" Subscriptions ");
+    if app.refreshing_feeds > 0 {
+        list_title.push_str(&format!("- refreshing {} ", app.refreshing_feeds));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .title(list_title);
+
+    if app.podcasts.podcasts.is_empty() {
+        let empty_msg = Paragraph::new("No podcasts yet. Press 'a' to subscribe to a feed.")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty_msg, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = app.podcasts.podcasts.iter().enumerate().map(|(i, podcast)| {
+            let is_selected = i == app.selected_podcast;
+            ListItem::new(vec![
+                Spans::from(vec![
+                    Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        &podcast.title,
+                        Style::default()
+                            .fg(if is_selected { Color::Yellow } else { Color::White })
+                            .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() })
+                    ),
+                ]),
+                Spans::from(vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        format!("{} episodes, {} unplayed", podcast.episodes.len(), podcast.unplayed()),
+                        Style::default().fg(Color::DarkGray)
+                    ),
+                ]),
+            ])
+        }).collect();
+
+        let mut state = ListState::default();
+        state.select(Some(app.selected_podcast));
+        f.render_stateful_widget(List::new(items).block(block), chunks[1], &mut state);
+    }
+
+    let controls = Paragraph::new(vec![
+            status_line(app),
+            Spans::from(vec![
+                Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Navigate   "),
+                Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Episodes   "),
+                Span::styled(" [a]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Subscribe   "),
+                Span::styled(" [r]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Refresh all   "),
+                Span::styled(" [d]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Unsubscribe   "),
+                Span::styled(" [b]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Back   "),
+            ]),
+        ])
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Controls "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls, chunks[2]);
+}
+
+fn draw_unsubscribe_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let Some(podcast) = app.podcasts.podcasts.get(app.selected_podcast) else { return };
+    let downloaded = podcast.episodes.iter().filter(|episode| episode.download_path.is_some()).count();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Length(5),     // Warning
+            Constraint::Min(5),        // Podcast info
+            Constraint::Length(3),     // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new("🎙 Music Player - Unsubscribe")
+        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)));
+
+    f.render_widget(title, chunks[0]);
+
+    let warning = Paragraph::new(vec![
+        Spans::from(vec![Span::raw("")]),
+        Spans::from(vec![
+            Span::styled("⚠ WARNING: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                "You are about to unsubscribe from this podcast.",
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        ]),
+        Spans::from(vec![Span::raw("Played and resume positions are forgotten.")]),
+    ])
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red)))
+    .alignment(Alignment::Center);
+
+    f.render_widget(warning, chunks[1]);
+
+    let info = Paragraph::new(vec![
+        Spans::from(vec![Span::raw("")]),
+        Spans::from(vec![
+            Span::styled("Title: ", Style::default().fg(Color::Gray)),
+            Span::styled(&podcast.title, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        ]),
+        Spans::from(vec![
+            Span::styled("Downloaded: ", Style::default().fg(Color::Gray)),
+            Span::styled(format!("{} episode(s)", downloaded), Style::default().fg(Color::White)),
+        ]),
+    ])
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Podcast to Remove "))
+    .alignment(Alignment::Center);
+
+    f.render_widget(info, chunks[2]);
+
+    let mut controls = vec![
+        Span::styled(" [y]", Style::default().fg(Color::Yellow)),
+        Span::raw(if downloaded > 0 { " Yes, keep downloads   " } else { " Yes, unsubscribe   " }),
+    ];
+    if downloaded > 0 {
+        controls.push(Span::styled(" [D]", Style::default().fg(Color::Yellow)));
+        controls.push(Span::raw(" Yes, delete downloads   "));
+    }
+    controls.push(Span::styled(" [n/Esc]", Style::default().fg(Color::Yellow)));
+    controls.push(Span::raw(" Cancel   "));
+
+    let controls_widget = Paragraph::new(vec![Spans::from(controls)])
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(" Confirm Unsubscribe "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls_widget, chunks[3]);
+}
+
+fn draw_episodes_mode<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let Some(podcast) = app.podcasts.podcasts.get(app.selected_podcast) else { return };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),     // Title
+            Constraint::Min(10),       // Episodes
+            Constraint::Length(4),     // Controls
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new(format!("🎙 {}", podcast.title))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)));
+
+    f.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = podcast.episodes.iter().enumerate().map(|(i, episode)| {
+        let is_selected = i == app.selected_episode;
+        let is_playing = app.playing_episode.as_ref()
+            .is_some_and(|(feed, guid)| *feed == podcast.feed_url && *guid == episode.guid);
+
+        // ● belum diputar, ✓ selesai, atau posisi resume
+        let (state_text, state_color) = if episode.played {
+            (String::from("✓"), Color::DarkGray)
+        } else if episode.position > 5.0 {
+            (format!("⏸ {}", podcast::format_duration(episode.position as u64)), Color::Cyan)
+        } else {
+            (String::from("●"), Color::Green)
+        };
+
+        let download_text = match app.downloads.get(&episode.guid) {
+            Some((done, Some(total))) if *total > 0 => format!("  ⬇ {}%", done * 100 / total),
+            Some((done, _)) => format!("  ⬇ {} KB", done / 1024),
+            None if episode.download_path.is_some() => String::from("  💾"),
+            None => String::new(),
+        };
+
+        let details = [
+            episode.published.map(podcast::format_date),
+            episode.duration.map(podcast::format_duration),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        ListItem::new(vec![
+            Spans::from(vec![
+                Span::styled(if is_selected { "▶ " } else { "  " }, Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} ", state_text), Style::default().fg(state_color)),
+                Span::styled(
+                    &episode.title,
+                    Style::default()
+                        .fg(if is_playing { Color::Green } else if is_selected { Color::Yellow } else { Color::White })
+                        .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() })
+                ),
+            ]),
+            Spans::from(vec![
+                Span::raw("    "),
+                Span::styled(details, Style::default().fg(Color::DarkGray)),
+                Span::styled(download_text, Style::default().fg(Color::Cyan)),
+            ]),
+        ])
+    }).collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.selected_episode));
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(format!(" Episodes - {} unplayed ", podcast.unplayed())));
+
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let controls = Paragraph::new(vec![
+            status_line(app),
+            Spans::from(vec![
+                Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Navigate   "),
+                Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Play/Resume   "),
+                Span::styled(" [d]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Download   "),
+                Span::styled(" [m]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Toggle played   "),
+                Span::styled(" [b]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Back   "),
+            ]),
+        ])
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(" Controls "))
+        .alignment(Alignment::Center);
+
+    f.render_widget(controls, chunks[2]);
+}
+
+/// Durasi singkat seperti `5m ago` untuk daftar riwayat
+fn format_ago(secs: u64) -> String {
+    match secs {
+        0..=59 => String::from("just now"),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+// === Messages ===
+fn draw_messages<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = get_layout(f.size());
+
+    let title = Paragraph::new("💬 Messages")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(title, chunks[0]);
+
+    let now = SystemTime::now();
+    let items: Vec<ListItem> = app.toasts.iter().map(|toast| {
+        let ago = now.duration_since(toast.time).map(|d| d.as_secs()).unwrap_or(0);
+        let mut spans = vec![
+            Span::styled(format!("{:>8}  ", format_ago(ago)), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", toast.level.icon()), Style::default().fg(level_color(toast.level))),
+            Span::raw(toast.message.clone()),
+        ];
+        if toast.repeats > 1 {
+            spans.push(Span::styled(format!("  (×{})", toast.repeats), Style::default().fg(Color::DarkGray)));
+        }
+        ListItem::new(Spans::from(spans))
+    }).collect();
+
+    let list_title = if app.toasts.is_empty() {
+        String::from(" No messages ")
+    } else {
+        format!(" {} messages, newest first ", app.toasts.len())
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(list_title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    if !app.toasts.is_empty() {
+        state.select(Some(app.selected_message));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let controls = Paragraph::new(Spans::from(vec![
+            Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Scroll   "),
+            Span::styled(" [c]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Clear   "),
+            Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back   "),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(controls, chunks[2]);
+}
+
+// === Downloads ===
+fn draw_downloads<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let prompt_height = if app.download_prompt.is_some() { 3 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(prompt_height),
+            Constraint::Length(4),
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new(format!(
+            "⬇ Downloads  —  preset: {}  —  {}",
+            app.download_preset.label(),
+            app.config.downloads.dir().display()
+        ))
+        .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Magenta)));
+    f.render_widget(title, chunks[0]);
+
+    let bar_width = (chunks[1].width as usize).saturating_sub(60).clamp(10, 40);
+    let items: Vec<ListItem> = app.downloader.jobs.iter().map(|job| {
+        ListItem::new(vec![
+            Spans::from(vec![
+                Span::styled(format!("#{:<3} ", job.id), Style::default().fg(Color::DarkGray)),
+                Span::styled(job.display_title().to_string(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  [{}]", job.preset.label()), Style::default().fg(Color::DarkGray)),
+            ]),
+            job_status_line(job, bar_width),
+        ])
+    }).collect();
+
+    let list_title = if app.downloader.jobs.is_empty() {
+        String::from(" No downloads yet — press [a] to add a URL ")
+    } else {
+        format!(
+            " {} running, {} queued (limit {}) ",
+            app.downloader.running(),
+            app.downloader.queued(),
+            app.config.downloads.concurrency.max(1)
+        )
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(list_title))
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    let mut state = ListState::default();
+    if !app.downloader.jobs.is_empty() {
+        state.select(Some(app.selected_job));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    if let Some(prompt) = app.download_prompt {
+        let label = match prompt {
+            DownloadPrompt::Url => " > URL(s) to download ",
+            DownloadPrompt::Playlist => " > Add to playlist (empty = main playlist) ",
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(label);
+        app.download_input.render(f, chunks[2], block, Style::default().fg(Color::Yellow), true);
+    }
+
+    let keys = if app.download_prompt.is_some() {
+        vec![
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Confirm   "),
+            Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel   "),
+        ]
+    } else {
+        vec![
+            Span::styled(" [a]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Add URL   "),
+            Span::styled(" [f]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Preset   "),
+            Span::styled(" [c]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel   "),
+            Span::styled(" [r]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Retry   "),
+            Span::styled(" [d]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Remove   "),
+            Span::styled(" [p]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Add to playlist   "),
+            Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back   "),
+        ]
+    };
+    let controls = Paragraph::new(vec![status_line(app), Spans::from(keys)])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Magenta)));
+    f.render_widget(controls, chunks[3]);
+}
+
+/// Baris kedua tiap job: progress bar saat berjalan, hasil atau error setelahnya
+fn job_status_line(job: &Job, bar_width: usize) -> Spans<'static> {
+    let indent = Span::raw("     ");
+    match &job.state {
+        JobState::Queued => Spans::from(vec![indent, Span::styled("queued", Style::default().fg(Color::DarkGray))]),
+        JobState::Running => {
+            let filled = ((job.progress.percent / 100.0) * bar_width as f64).round() as usize;
+            let filled = filled.min(bar_width);
+            let mut details = vec![format!("{:5.1}%", job.progress.percent)];
+            details.extend(job.progress.total.as_ref().map(|total| format!("of {}", total)));
+            details.extend(job.progress.speed.as_ref().map(|speed| format!("at {}", speed)));
+            details.extend(job.progress.eta.as_ref().map(|eta| format!("ETA {}", eta)));
+            if let Some(stage) = &job.stage {
+                details.push(stage.clone());
+            }
+            Spans::from(vec![
+                indent,
+                Span::styled("█".repeat(filled), Style::default().fg(Color::Magenta)),
+                Span::styled("░".repeat(bar_width - filled), Style::default().fg(Color::DarkGray)),
+                Span::raw(format!(" {}", details.join(" "))),
+            ])
+        }
+        JobState::Finished(path) => {
+            let took = match (job.started, job.finished) {
+                (Some(started), Some(finished)) => format!(" in {}", HumanDuration(finished - started)),
+                _ => String::new(),
+            };
+            Spans::from(vec![
+                indent,
+                Span::styled(format!("✔ done{}  ", took), Style::default().fg(Color::Green)),
+                Span::styled(path.display().to_string(), Style::default().fg(Color::DarkGray)),
+            ])
+        }
+        JobState::Failed(error) => Spans::from(vec![
+            indent,
+            Span::styled(format!("✖ {}", error), Style::default().fg(Color::Red)),
+        ]),
+        JobState::Cancelled => Spans::from(vec![indent, Span::styled("cancelled", Style::default().fg(Color::Yellow))]),
+    }
+}
+
+// === Settings ===
+fn draw_settings<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(4),
+        ])
+        .split(f.size());
+
+    let title = Paragraph::new("⚙ Settings")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(title, chunks[0]);
+
+    let active = app.active_audio_device();
+    let items: Vec<ListItem> = app.audio_devices.iter().map(|device| {
+        let is_active = device.name == active;
+        ListItem::new(Spans::from(vec![
+            Span::styled(if is_active { "● " } else { "  " }, Style::default().fg(Color::Green)),
+            Span::styled(
+                device.description.clone(),
+                if is_active { Style::default().add_modifier(Modifier::BOLD) } else { Style::default() }
+            ),
+            Span::styled(format!("  {}", device.name), Style::default().fg(Color::DarkGray)),
+        ]))
+    }).collect();
+
+    let list_title = match (&app.device_error, app.audio_devices.is_empty()) {
+        (Some(error), _) => format!(" Audio output device — {} ", error),
+        (None, true) => String::from(" Audio output device — scanning… "),
+        (None, false) => String::from(" Audio output device "),
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if app.device_error.is_some() { Color::Red } else { Color::Cyan }))
+            .title(list_title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    if !app.audio_devices.is_empty() {
+        state.select(Some(app.selected_device));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let controls = Paragraph::new(vec![
+            status_line(app),
+            Spans::from(vec![
+                Span::styled(" [↑/↓]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Select   "),
+                Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Use device   "),
+                Span::styled(" [r]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Rescan   "),
+                Span::styled(" [Esc]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Back   "),
+            ]),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(controls, chunks[2]);
+}
+
+// === Logs ===
+fn draw_logs<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let view = &app.log_view;
+    let show_search = view.searching || !view.search.value().is_empty();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),                                  // Title
+            Constraint::Min(5),                                     // Log
+            Constraint::Length(if show_search { 3 } else { 0 }),    // Search
+            Constraint::Length(3),                                  // Controls
+        ])
+        .split(f.size());
+
+    let path = logging::log_path().map(|path| path.display().to_string()).unwrap_or_default();
+    let title = Paragraph::new(vec![Spans::from(vec![
+            Span::styled("📜 Logs  ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(path, Style::default().fg(Color::DarkGray)),
+        ])])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(title, chunks[0]);
+
+    let visible = view.visible();
+    let items: Vec<ListItem> = visible.iter().map(|line| {
+        let color = match line.level {
+            Some(log::Level::Error) => Color::Red,
+            Some(log::Level::Warn) => Color::Yellow,
+            Some(log::Level::Info) | None => Color::White,
+            Some(log::Level::Debug) => Color::Gray,
+            Some(log::Level::Trace) => Color::DarkGray,
+        };
+        ListItem::new(Span::styled(line.text.as_str(), Style::default().fg(color)))
+    }).collect();
+
+    let list_title = match &view.error {
+        Some(error) => format!(" {} ", error),
+        None => format!(" {} of {} lines ", visible.len(), view.lines.len()),
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(list_title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    if !visible.is_empty() {
+        state.select(Some(view.selected));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    if show_search {
+        let color = if view.searching { Color::Yellow } else { Color::Cyan };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .title(" Search ");
+        view.search.render(f, chunks[2], block, Style::default().fg(Color::White), view.searching);
+    }
+
+    let key = Style::default().fg(Color::Yellow);
+    let controls = Paragraph::new(Spans::from(vec![
+            Span::styled(" [↑/↓/PgUp/PgDn]", key),
+            Span::raw(" Scroll   "),
+            Span::styled(" [l]", key),
+            Span::raw(format!(" Level: {}   ", view.level.as_str().to_lowercase())),
+            Span::styled(" [/]", key),
+            Span::raw(" Search   "),
+            Span::styled(" [f]", key),
+            Span::raw(if view.follow { " Follow: on   " } else { " Follow: off   " }),
+            Span::styled(" [r]", key),
+            Span::raw(" Reload   "),
+            Span::styled(" [Esc]", key),
+            Span::raw(" Back   "),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+    f.render_widget(controls, chunks[3]);
+}
+
 // === Feature Screens ===
 fn draw_feature_screen<B: Backend>(f: &mut Frame<B>, title: &str, color: Color, features: Vec<&str>) {
     let chunks = get_layout(f.size());
@@ -558,7 +1933,6 @@ fn get_coming_soon_features() -> Vec<&'static str> {
         "Future tools that will be added to TERMINUS:", "",
         "• Markdown viewer and editor",
         "• API tester for developers",
-        "• YouTube downloader",
         "• Terminal-based chat client",
         "• System monitor dashboard",
         "• Notes and todo manager", "",
@@ -0,0 +1,2 @@
+// src/widgets/mod.rs
+pub mod text_input;
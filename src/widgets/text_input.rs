@@ -0,0 +1,257 @@
+// src/widgets/text_input.rs
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Single-line text input dengan cursor, dipakai oleh semua form di app.
+///
+/// `cursor` selalu berupa byte offset yang jatuh di batas grapheme, jadi
+/// judul non-ASCII (emoji, huruf beraksen, CJK) diedit per karakter yang terlihat.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_value(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.len();
+        Self { value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    /// Proses satu key event. Return `true` kalau key dipakai oleh input.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('w') if ctrl => self.delete_word_before(),
+            KeyCode::Char('u') if ctrl => self.delete_to_start(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.value.len(),
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace => self.delete_before(),
+            KeyCode::Delete => self.delete_after(),
+            KeyCode::Left if ctrl => self.cursor = self.word_start_before(),
+            KeyCode::Right if ctrl => self.cursor = self.word_end_after(),
+            KeyCode::Left => self.cursor = self.prev_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Sisipkan teks hasil paste. Newline diganti spasi karena input ini satu baris.
+    pub fn insert_str(&mut self, text: &str) {
+        let cleaned: String = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|c| if c == '\n' || c == '\r' || c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        self.value.insert_str(self.cursor, &cleaned);
+        self.cursor += cleaned.len();
+    }
+
+    fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn delete_before(&mut self) {
+        let start = self.prev_boundary();
+        self.value.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    fn delete_after(&mut self) {
+        let end = self.next_boundary();
+        self.value.replace_range(self.cursor..end, "");
+    }
+
+    fn delete_word_before(&mut self) {
+        let start = self.word_start_before();
+        self.value.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    fn delete_to_start(&mut self) {
+        self.value.replace_range(..self.cursor, "");
+        self.cursor = 0;
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map(|g| self.cursor + g.len())
+            .unwrap_or(self.cursor)
+    }
+
+    fn word_start_before(&self) -> usize {
+        let before = &self.value[..self.cursor];
+        let trimmed = before.trim_end();
+        trimmed
+            .rfind(char::is_whitespace)
+            .map(|i| i + trimmed[i..].chars().next().map_or(1, char::len_utf8))
+            .unwrap_or(0)
+    }
+
+    fn word_end_after(&self) -> usize {
+        let after = &self.value[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        after[skipped..]
+            .find(char::is_whitespace)
+            .map(|i| self.cursor + skipped + i)
+            .unwrap_or(self.value.len())
+    }
+
+    /// Potongan teks yang muat di `width` kolom beserta posisi kolom cursor.
+    /// Scroll horizontal dihitung dari posisi cursor, jadi tidak perlu state tambahan.
+    pub fn visible(&self, width: usize) -> (String, usize) {
+        if width == 0 {
+            return (String::new(), 0);
+        }
+        let cursor_col = self.value[..self.cursor].width();
+        // Sisakan satu kolom supaya cursor di akhir teks tetap kelihatan
+        let scroll = cursor_col.saturating_sub(width - 1);
+
+        let mut col = 0;
+        let mut shown = String::new();
+        for g in self.value.graphemes(true) {
+            let w = g.width();
+            if col >= scroll && col + w <= scroll + width {
+                shown.push_str(g);
+            } else if col + w > scroll + width {
+                break;
+            }
+            col += w;
+        }
+        (shown, cursor_col - scroll)
+    }
+
+    /// Render input di dalam `block`. Cursor terminal hanya ditampilkan kalau `focused`.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect, block: Block, style: Style, focused: bool) {
+        let inner = block.inner(area);
+        let (shown, cursor_x) = self.visible(inner.width as usize);
+        f.render_widget(Paragraph::new(shown).style(style).block(block), area);
+        if focused && inner.width > 0 && inner.height > 0 {
+            f.set_cursor(inner.x + cursor_x as u16, inner.y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn ctrl(input: &mut TextInput, c: char) {
+        input.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn cursor_moves_and_deletes_whole_graphemes() {
+        // "e" + combining acute, lalu emoji keluarga (beberapa code point dengan ZWJ)
+        let mut input = TextInput::with_value("ae\u{301}👨‍👩‍👧b");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "a👨‍👩‍👧b");
+
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.value(), "ab");
+
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Char('x'));
+        assert_eq!(input.value(), "xab");
+    }
+
+    #[test]
+    fn ctrl_w_deletes_previous_word_and_stops_at_edges() {
+        let mut input = TextInput::with_value("foo bar  ");
+        ctrl(&mut input, 'w');
+        assert_eq!(input.value(), "foo ");
+        ctrl(&mut input, 'w');
+        assert_eq!(input.value(), "");
+        ctrl(&mut input, 'w');
+        assert_eq!(input.value(), "");
+
+        // Di awal teks tidak ada yang dihapus
+        let mut input = TextInput::with_value("foo bar");
+        press(&mut input, KeyCode::Home);
+        ctrl(&mut input, 'w');
+        assert_eq!(input.value(), "foo bar");
+    }
+
+    #[test]
+    fn ctrl_u_deletes_up_to_the_cursor() {
+        let mut input = TextInput::with_value("hello world");
+        for _ in 0..5 {
+            press(&mut input, KeyCode::Left);
+        }
+        ctrl(&mut input, 'u');
+        assert_eq!(input.value(), "world");
+        press(&mut input, KeyCode::Char('>'));
+        assert_eq!(input.value(), ">world");
+    }
+
+    #[test]
+    fn pasted_newlines_become_spaces() {
+        let mut input = TextInput::with_value("[]");
+        press(&mut input, KeyCode::Left);
+        input.insert_str("one\ntwo\tthree\r\n");
+        assert_eq!(input.value(), "[one two three]");
+        press(&mut input, KeyCode::Char('!'));
+        assert_eq!(input.value(), "[one two three!]");
+    }
+
+    #[test]
+    fn visible_scrolls_to_keep_the_cursor_in_view() {
+        let mut input = TextInput::with_value("abcdefghij");
+        assert_eq!(input.visible(5), (String::from("ghij"), 4));
+
+        press(&mut input, KeyCode::Home);
+        assert_eq!(input.visible(5), (String::from("abcde"), 0));
+
+        // Karakter lebar dihitung dua kolom
+        let input = TextInput::with_value("日本語");
+        assert_eq!(input.visible(4), (String::from("語"), 3));
+        assert_eq!(input.visible(0), (String::new(), 0));
+    }
+}